@@ -2,6 +2,8 @@ use super::*;
 
 #[derive(Debug)]
 pub struct ConstDecl {
+    pub docs: Vec<String>,
+    pub is_pub: bool,
     pub name: String,
     pub initial_value: ConstInitialValue,
     pub span: Span,
@@ -10,9 +12,20 @@ pub struct ConstDecl {
 #[derive(Debug)]
 pub enum ConstInitialValueEnum {
     Function(FunctionDef),
+    Struct(StructDef),
+    Enum(EnumDef),
+    Union(UnionDef),
+    Import(Import),
     Exp(ConstExp),
 }
 
+// `@import("path")`, with the path relative to the importing file.
+#[derive(Debug)]
+pub struct Import {
+    pub path: Str,
+    pub span: Span,
+}
+
 #[derive(Debug)]
 pub struct ConstInitialValue {
     pub value: ConstInitialValueEnum,
@@ -29,6 +42,7 @@ pub struct FunctionDef {
 
 #[derive(Debug)]
 pub struct Param {
+    pub is_const: bool,
     pub name: String,
     pub param_type: Type,
     pub span: Span,
@@ -36,16 +50,54 @@ pub struct Param {
 
 #[derive(Debug)]
 pub struct VarDecl {
+    pub docs: Vec<String>,
+    pub is_pub: bool,
     pub name: String,
-    pub var_type: Type,
+    // `None` when the type is inferred from the initial value.
+    pub var_type: Option<Type>,
     pub initial_value: Exp,
     pub span: Span,
 }
 
 #[derive(Debug)]
 pub struct ExternFunctionDef {
+    pub docs: Vec<String>,
+    pub is_pub: bool,
     pub name: String,
     pub params: Vec<Param>,
     pub return_type: Type,
     pub span: Span,
 }
+
+#[derive(Debug)]
+pub struct StructDef {
+    pub fields: Vec<Field>,
+    pub span: Span,
+}
+
+#[derive(Debug)]
+pub struct Field {
+    pub name: String,
+    pub field_type: Type,
+    pub span: Span,
+}
+
+#[derive(Debug)]
+pub struct EnumDef {
+    pub backing_type: Type,
+    pub variants: Vec<Variant>,
+    pub span: Span,
+}
+
+#[derive(Debug)]
+pub struct Variant {
+    pub name: String,
+    pub value: Option<Exp>,
+    pub span: Span,
+}
+
+#[derive(Debug)]
+pub struct UnionDef {
+    pub fields: Vec<Field>,
+    pub span: Span,
+}
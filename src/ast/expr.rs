@@ -30,8 +30,11 @@ impl Array {
 pub enum Exp {
     Exp(Box<Exp>, Span),
     Number(Number),
+    Float(Box<Float>),
+    Bool(bool, Span),
+    Char(Char),
     LVal(Box<LVal>),
-    Str(String, Span),
+    Str(Box<Str>),
     Unary(UnaryOp, Box<Exp>, Span),
     Binary(Box<Exp>, BinaryOp, Box<Exp>, Span),
     GetAddr(Box<GetAddr>),
@@ -39,6 +42,8 @@ pub enum Exp {
     Array(Box<Array>),
     ConvertType(Box<ConvertType>),
     Call(Box<Call>),
+    Struct(Box<StructLiteral>),
+    Slice(Box<Slice>),
 }
 
 impl Exp {
@@ -46,15 +51,20 @@ impl Exp {
         match self {
             Exp::Exp(_, span) => span.clone(),
             Exp::Number(number) => number.span.clone(),
+            Exp::Float(float) => float.span.clone(),
+            Exp::Bool(_, span) => span.clone(),
+            Exp::Char(char) => char.span.clone(),
             Exp::LVal(lval) => lval.span.clone(),
             Exp::Unary(_, _, span) => span.clone(),
             Exp::Binary(_, _, _, span) => span.clone(),
             Exp::GetAddr(get_addr) => get_addr.span.clone(),
-            Exp::Str(_, span) => span.clone(),
+            Exp::Str(str) => str.span.clone(),
             Exp::Deref(deref) => deref.get_span(),
             Exp::Array(array) => array.get_span(),
             Exp::ConvertType(convert_type) => convert_type.span.clone(),
             Exp::Call(call) => call.span.clone(),
+            Exp::Struct(literal) => literal.span.clone(),
+            Exp::Slice(slice) => slice.span.clone(),
         }
     }
 }
@@ -66,6 +76,27 @@ pub struct Call {
     pub span: Span,
 }
 
+#[derive(Debug)]
+pub struct StructLiteral {
+    pub ids: Vec<String>,
+    pub fields: Vec<FieldInit>,
+    pub span: Span,
+}
+
+#[derive(Debug)]
+pub struct FieldInit {
+    pub name: String,
+    pub value: Exp,
+    pub span: Span,
+}
+
+#[derive(Debug)]
+pub struct Slice {
+    pub value: Exp,
+    pub range: Range,
+    pub span: Span,
+}
+
 #[derive(Debug)]
 pub struct LVal {
     pub ids: Vec<String>,
@@ -94,7 +125,7 @@ impl Deref {
 
 #[derive(Debug)]
 pub struct GetAddr {
-    pub lval: LVal,
+    pub exp: Exp,
     pub span: Span,
 }
 
@@ -105,17 +136,50 @@ pub struct ConvertType {
     pub span: Span,
 }
 
+// Without a suffix such as `10u8` a literal is an `i64`. The value is wider
+// than `u64` so that oversized literals can be reported instead of wrapping.
 #[derive(Debug)]
 pub struct Number {
-    pub num: u64,
+    pub num: u128,
+    pub suffix: Option<IntSuffix>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone)]
+pub struct IntSuffix {
+    pub bit_width: u32,
+    pub signed: bool,
+}
+
+// `unicode` marks a code point, which is `u32` instead of a `u8` byte.
+#[derive(Debug)]
+pub struct Char {
+    pub num: u32,
+    pub unicode: bool,
+    pub span: Span,
+}
+
+// `invalid` keeps the first `\u` escape that is not a unicode scalar value.
+#[derive(Debug)]
+pub struct Str {
+    pub value: String,
+    pub invalid: Option<u32>,
     pub span: Span,
 }
 
 #[derive(Debug)]
+pub struct Float {
+    pub num: f64,
+    pub text: String,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone)]
 pub enum UnaryOp {
     Positive,
     Negative,
     Not,
+    BitNot,
 }
 
 #[derive(Debug, Clone)]
@@ -131,4 +195,11 @@ pub enum BinaryOp {
     Ge,
     Eq,
     Neq,
+    BitAnd,
+    BitOr,
+    BitXor,
+    Shl,
+    Shr,
+    And,
+    Or,
 }
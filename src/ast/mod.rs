@@ -36,8 +36,6 @@ impl Span {
         f: &mut core::fmt::Formatter<'_>,
         error_string: String,
     ) -> core::fmt::Result {
-        let num_len = format!("{}", self.start.0).len();
-
         writeln!(
             f,
             "{}{} {}",
@@ -46,6 +44,18 @@ impl Span {
             error_string.bold()
         )?;
 
+        self.show_source(f, true)
+    }
+
+    pub fn show_note(&self, f: &mut core::fmt::Formatter<'_>, note: String) -> core::fmt::Result {
+        writeln!(f, "{}{} {}", "note".blue().bold(), ":".bold(), note.bold())?;
+
+        self.show_source(f, false)
+    }
+
+    fn show_source(&self, f: &mut core::fmt::Formatter<'_>, primary: bool) -> core::fmt::Result {
+        let num_len = format!("{}", self.start.0).len();
+
         for _ in 0..num_len {
             write!(f, " ")?;
         }
@@ -73,7 +83,11 @@ impl Span {
         }
 
         for _ in self.start.1..self.end.1 {
-            write!(f, "{}", "^".red().bold())?;
+            if primary {
+                write!(f, "{}", "^".red().bold())?;
+            } else {
+                write!(f, "{}", "-".blue().bold())?;
+            }
         }
         Ok(())
     }
@@ -30,10 +30,72 @@ pub enum Decl {
     ExternFunctionDef(ExternFunctionDef),
 }
 
+impl CompUnit {
+    // Imports are only resolved at the top level of a file.
+    pub fn imports(&self) -> impl Iterator<Item = &Import> {
+        self.global_items
+            .iter()
+            .filter_map(|global_item| match global_item {
+                GlobalItem::Decl(Decl::ConstDecl(ConstDecl {
+                    initial_value:
+                        ConstInitialValue {
+                            value: ConstInitialValueEnum::Import(import),
+                            ..
+                        },
+                    ..
+                })) => Some(import),
+                _ => None,
+            })
+    }
+}
+
+impl Decl {
+    pub fn name(&self) -> &str {
+        match self {
+            Decl::VarDecl(var_decl) => &var_decl.name,
+            Decl::ConstDecl(const_decl) => &const_decl.name,
+            Decl::ExternFunctionDef(extern_function_def) => &extern_function_def.name,
+        }
+    }
+
+    pub fn is_pub(&self) -> bool {
+        match self {
+            Decl::VarDecl(var_decl) => var_decl.is_pub,
+            Decl::ConstDecl(const_decl) => const_decl.is_pub,
+            Decl::ExternFunctionDef(extern_function_def) => extern_function_def.is_pub,
+        }
+    }
+
+    pub fn get_span(&self) -> Span {
+        match self {
+            Decl::VarDecl(var_decl) => var_decl.span.clone(),
+            Decl::ConstDecl(const_decl) => const_decl.span.clone(),
+            Decl::ExternFunctionDef(extern_function_def) => extern_function_def.span.clone(),
+        }
+    }
+
+    // The `///` comments written before the declaration, one per line.
+    pub fn docs(&self) -> &[String] {
+        match self {
+            Decl::VarDecl(var_decl) => &var_decl.docs,
+            Decl::ConstDecl(const_decl) => &const_decl.docs,
+            Decl::ExternFunctionDef(extern_function_def) => &extern_function_def.docs,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum Statement {
     Return(Return),
     Exp(Option<Exp>),
+    If(Box<If>),
+    While(Box<While>),
+    For(Box<For>),
+    Match(Box<Match>),
+    Switch(Box<Switch>),
+    Assign(Box<Assign>),
+    Break(Break),
+    Continue(Continue),
 }
 
 #[derive(Debug)]
@@ -41,3 +103,110 @@ pub struct Return {
     pub value: Option<Exp>,
     pub span: Span,
 }
+
+#[derive(Debug)]
+pub struct If {
+    pub condition: Exp,
+    pub then_block: Block,
+    pub else_block: Option<Else>,
+    pub span: Span,
+}
+
+#[derive(Debug)]
+pub enum Else {
+    If(Box<If>),
+    Block(Block),
+}
+
+#[derive(Debug)]
+pub struct While {
+    pub label: Option<Label>,
+    pub condition: Exp,
+    pub block: Block,
+    pub span: Span,
+}
+
+#[derive(Debug)]
+pub struct For {
+    pub label: Option<Label>,
+    pub var: String,
+    pub iter: ForIter,
+    pub block: Block,
+    pub span: Span,
+}
+
+#[derive(Debug)]
+pub enum ForIter {
+    Range(Range),
+    Array(Exp),
+}
+
+#[derive(Debug)]
+pub struct Range {
+    pub start: Exp,
+    pub end: Exp,
+    pub span: Span,
+}
+
+#[derive(Debug)]
+pub struct Assign {
+    pub target: Exp,
+    pub op: Option<BinaryOp>,
+    pub value: Exp,
+    pub span: Span,
+}
+
+#[derive(Debug)]
+pub struct Break {
+    pub label: Option<Label>,
+    pub span: Span,
+}
+
+#[derive(Debug)]
+pub struct Continue {
+    pub label: Option<Label>,
+    pub span: Span,
+}
+
+#[derive(Debug)]
+pub struct Label {
+    pub name: String,
+    pub span: Span,
+}
+
+#[derive(Debug)]
+pub struct Match {
+    pub value: Exp,
+    pub arms: Vec<MatchArm>,
+    pub else_block: Option<Block>,
+    pub span: Span,
+}
+
+#[derive(Debug)]
+pub struct MatchArm {
+    pub variant: String,
+    pub binding: Option<String>,
+    pub block: Block,
+    pub span: Span,
+}
+
+#[derive(Debug)]
+pub struct Switch {
+    pub value: Exp,
+    pub arms: Vec<SwitchArm>,
+    pub else_block: Option<Block>,
+    pub span: Span,
+}
+
+#[derive(Debug)]
+pub struct SwitchArm {
+    pub cases: Vec<SwitchCase>,
+    pub block: Block,
+    pub span: Span,
+}
+
+#[derive(Debug)]
+pub enum SwitchCase {
+    Value(Exp),
+    Range(Range),
+}
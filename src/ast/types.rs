@@ -10,12 +10,27 @@ pub enum TypeEnum {
     I32,
     I16,
     I8,
+    F64,
+    F32,
+    Bool,
     Void,
+    // A name, possibly qualified by modules as in `io.File`.
+    Named(Vec<String>),
+    Array(Box<Type>, Box<ConstExp>),
+    // The flag marks `[]const T`.
+    Slice(Box<Type>, bool),
 }
 
 #[derive(Debug)]
 pub struct Type {
     pub ty: TypeEnum,
-    pub star: usize,
+    pub star: Vec<Star>,
     pub span: Span,
 }
+
+// One level of pointer, qualifying what it points to.
+#[derive(Debug)]
+pub struct Star {
+    pub is_const: bool,
+    pub is_volatile: bool,
+}
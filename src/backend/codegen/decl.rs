@@ -22,28 +22,68 @@ impl Codegen for ConstDecl {
         let value = self.initial_value.codegen(context.clone())?.clone();
 
         let mut context = context.write();
+        // Types declared in functions are hoisted to the top level of the C
+        // program and constants can be shadowed in nested blocks, so each one
+        // needs a name of its own.
+        let c_name = if context.local.len() != 0 {
+            context.new_temp_name(&alloc::format!("{}_", self.name))
+        } else {
+            context.c_name(&self.name)
+        };
+
+        // Types only exist at compile time, so the symbol refers to the type
+        // itself instead of a C object.
+        if let Ok(mut type_) = value.as_type(self.span.clone()) {
+            let value = if type_.set_type_name(c_name) {
+                context.c_program.insert_type(type_.clone());
+                Value::new_type(type_)
+            } else {
+                value
+            };
+
+            if context.local.len() != 0 {
+                context.local.push(Symbol::Const(self.name.clone(), value));
+            } else {
+                context.global.push(Symbol::Const(self.name.clone(), value));
+            }
+
+            return Ok(());
+        }
 
-        if value.get_type().is_const() {
-            context.c_program.insert_variable(CVariable::new(
-                self.name.clone(),
+        value.check_initializer(self.span.clone())?;
+
+        let const_decl = if value.get_type().is_const() {
+            CDeclaration::VariableDef(CVariable::new(
+                c_name.clone(),
                 value.clone(),
                 value.get_type(),
-            ));
+            ))
         } else {
-            context
-                .c_program
-                .insert_const(CConst::new(self.name.clone(), value.clone()));
+            CDeclaration::ConstDef(CConst::new(c_name.clone(), value.clone()))
+        };
+        // Constants in functions can refer to local variables.
+        if context.current_function.is_some() {
+            context.insert_block_item(CBlockItem::Decl(const_decl));
+        } else {
+            context.c_program.insert_decl(const_decl);
         }
 
+        // Constant arrays keep their qualifier so slices of them stay
+        // read-only.
+        let type_ = if value.get_type().is_array() {
+            CType::new_const(value.get_type())
+        } else {
+            value.get_type()
+        };
         if context.local.len() != 0 {
             context.local.push(Symbol::Const(
                 self.name.clone(),
-                Value::new_identifier(CIdentifier::new(self.name.clone(), value.get_type())),
+                Value::new_identifier(CIdentifier::new(c_name, type_)),
             ));
         } else {
             context.global.push(Symbol::Const(
                 self.name.clone(),
-                Value::new_identifier(CIdentifier::new(self.name.clone(), value.get_type())),
+                Value::new_identifier(CIdentifier::new(c_name, type_)),
             ));
         }
 
@@ -55,19 +95,39 @@ impl Codegen for VarDecl {
     type Target = ();
 
     fn codegen(&self, context: Arc<RwLock<CodegenContext>>) -> anyhow::Result<Self::Target> {
-        let value = self.initial_value.codegen(context.clone())?.clone();
-        let type_ = self.var_type.codegen(context.clone())?;
+        let value = self.initial_value.codegen(context.clone())?;
+        let type_ = match &self.var_type {
+            Some(var_type) => var_type.codegen(context.clone())?,
+            None => {
+                let type_ = value.get_type().without_const();
+                if type_.is_void() || type_.is_type() || type_.is_module() {
+                    return Err(CompileError::new_cannot_infer(
+                        self.initial_value.get_span(),
+                        type_.get_name(),
+                    )
+                    .into());
+                }
+                type_
+            }
+        };
+        let value = value.coerce(&type_, self.initial_value.get_span())?;
+        type_.check_assignable(&value.get_type(), self.initial_value.get_span())?;
+        value.check_initializer(self.initial_value.get_span())?;
+
+        let mut context = context.write();
+        let c_name = if context.current_function.is_some() {
+            self.name.clone()
+        } else {
+            context.c_name(&self.name)
+        };
 
         let var_decl =
-            CDeclaration::VariableDef(CVariable::new(self.name.clone(), value, type_.clone()));
+            CDeclaration::VariableDef(CVariable::new(c_name.clone(), value, type_.clone()));
 
-        let var_id = Value::new_identifier(CIdentifier::new(self.name.clone(), type_));
+        let var_id = Value::new_identifier(CIdentifier::new(c_name, type_));
 
-        let mut context = context.write();
-        if let Some(current_function) = context.current_function.clone() {
-            let current_function = current_function.get_name();
-            let function = context.c_program.function_mut(current_function).unwrap();
-            function.body.push(CBlockItem::Decl(var_decl));
+        if context.current_function.is_some() {
+            context.insert_block_item(CBlockItem::Decl(var_decl));
 
             context.local.push(Symbol::Var(self.name.clone(), var_id));
         } else {
@@ -86,30 +146,156 @@ impl Codegen for ConstInitialValue {
     fn codegen(&self, context: Arc<RwLock<CodegenContext>>) -> anyhow::Result<Value> {
         match &self.value {
             ConstInitialValueEnum::Exp(exp) => exp.codegen(context.clone()),
+            ConstInitialValueEnum::Import(import) => context
+                .read()
+                .get_import(&import.path.value, import.span.clone()),
             ConstInitialValueEnum::Function(func) => Ok(func.codegen(context.clone())?),
+            ConstInitialValueEnum::Struct(struct_def) => struct_def.codegen(context.clone()),
+            ConstInitialValueEnum::Enum(enum_def) => enum_def.codegen(context.clone()),
+            ConstInitialValueEnum::Union(union_def) => union_def.codegen(context.clone()),
         }
     }
 }
 
-impl Codegen for FunctionDef {
+impl Codegen for UnionDef {
+    type Target = Value;
+
+    fn codegen(&self, context: Arc<RwLock<CodegenContext>>) -> anyhow::Result<Value> {
+        let mut variants: Vec<(String, CType)> = Vec::new();
+
+        for field in self.fields.iter() {
+            if variants.iter().any(|(name, _)| *name == field.name) {
+                return Err(CompileError::new_duplicate_variant(
+                    field.span.clone(),
+                    field.name.clone(),
+                )
+                .into());
+            }
+            variants.push((
+                field.name.clone(),
+                field.field_type.codegen(context.clone())?,
+            ));
+        }
+
+        Ok(Value::new_type(CType::new_union(
+            CUnionType::new(String::new()),
+            variants,
+        )))
+    }
+}
+
+impl Codegen for EnumDef {
     type Target = Value;
 
     fn codegen(&self, context: Arc<RwLock<CodegenContext>>) -> anyhow::Result<Value> {
-        let func_type = self
-            .return_type
-            .codegen(context.clone())?
-            .function_type(Vec::new(), self.span.clone())?;
+        let backing_type = self.backing_type.codegen(context.clone())?;
+        let int_type = backing_type.as_int(self.backing_type.span.clone())?;
+
+        let mut variants: Vec<(String, i128)> = Vec::new();
+        let mut next = 0i128;
+
+        for variant in self.variants.iter() {
+            if variants.iter().any(|(name, _)| *name == variant.name) {
+                return Err(CompileError::new_duplicate_variant(
+                    variant.span.clone(),
+                    variant.name.clone(),
+                )
+                .into());
+            }
+
+            let value = match &variant.value {
+                Some(exp) => {
+                    let value = exp.codegen(context.clone())?;
+                    match value.int_literal() {
+                        Some(value) => value,
+                        // Variants of other enums can be reused as values.
+                        None => value.get_type().discriminant(value.as_int(exp.get_span())?),
+                    }
+                }
+                None => next,
+            };
+            if value < int_type.min() || value > int_type.max() {
+                return Err(CompileError::new_discriminant_overflow(
+                    variant.span.clone(),
+                    value,
+                    alloc::format!("{}", backing_type),
+                )
+                .into());
+            }
+            if let Some((name, _)) = variants.iter().find(|(_, other)| *other == value) {
+                return Err(CompileError::new_duplicate_discriminant(
+                    variant.span.clone(),
+                    value,
+                    name.clone(),
+                )
+                .into());
+            }
 
+            next = value + 1;
+            variants.push((variant.name.clone(), value));
+        }
+
+        // Negative discriminants are stored as their two's complement.
+        Ok(Value::new_type(CType::new_enum(
+            CEnumType::new(String::new(), int_type),
+            variants
+                .into_iter()
+                .map(|(name, value)| (name, value as u64))
+                .collect(),
+        )))
+    }
+}
+
+impl Codegen for StructDef {
+    type Target = Value;
+
+    fn codegen(&self, context: Arc<RwLock<CodegenContext>>) -> anyhow::Result<Value> {
+        let mut fields: Vec<(String, CType)> = Vec::new();
+
+        for field in self.fields.iter() {
+            if fields.iter().any(|(name, _)| *name == field.name) {
+                return Err(CompileError::new_duplicate_field(
+                    field.span.clone(),
+                    field.name.clone(),
+                )
+                .into());
+            }
+            fields.push((
+                field.name.clone(),
+                field.field_type.codegen(context.clone())?,
+            ));
+        }
+
+        Ok(Value::new_type(CType::new_struct(
+            CStructType::new(String::new()),
+            fields,
+        )))
+    }
+}
+
+impl Codegen for FunctionDef {
+    type Target = Value;
+
+    fn codegen(&self, context: Arc<RwLock<CodegenContext>>) -> anyhow::Result<Value> {
         let params = self
             .params
             .iter()
             .map(|param| {
-                (
-                    param.param_type.codegen(context.clone()).unwrap(),
+                Ok((
+                    param.param_type.codegen(context.clone())?,
                     param.name.clone(),
-                )
+                ))
             })
-            .collect::<Vec<_>>();
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        let return_type = self.return_type.codegen(context.clone())?;
+        let func_type = return_type.function_type(
+            params
+                .iter()
+                .map(|(param_type, _)| param_type.clone())
+                .collect(),
+            self.span.clone(),
+        )?;
 
         let mut context_write = context.write();
 
@@ -124,10 +310,10 @@ impl Codegen for FunctionDef {
 
         context_write.c_program.insert_function(CFunction::new(
             name.clone(),
-            self.return_type.codegen(context.clone())?,
+            return_type,
             true,
             false,
-            params,
+            params.clone(),
         ));
 
         let function = CIdentifier::new(name.clone(), func_type.clone());
@@ -137,13 +323,31 @@ impl Codegen for FunctionDef {
             CType::new_const(func_type.clone()),
         );
 
-        context_write.current_function = Some(function.clone());
+        let outer_function = context_write.current_function.replace(function.clone());
+        let outer_loops = core::mem::take(&mut context_write.loops);
+
+        context_write.push_scope();
+        for (param, (param_type, name)) in self.params.iter().zip(params) {
+            let value = Value::new_identifier(CIdentifier::new(name.clone(), param_type));
+            context_write.local.push(if param.is_const {
+                Symbol::Const(name, value)
+            } else {
+                Symbol::Var(name, value)
+            });
+        }
 
         drop(context_write);
 
-        self.block.codegen(context.clone())?;
+        let body = self.block.codegen(context.clone());
 
-        context.write().current_function = None;
+        let mut context_write = context.write();
+        for _ in self.params.iter() {
+            context_write.local.pop();
+        }
+        context_write.pop_scope();
+        context_write.current_function = outer_function;
+        context_write.loops = outer_loops;
+        context_write.c_program.function_mut(name).unwrap().body = body?;
 
         Ok(Value::new_convert_type(convert))
     }
@@ -153,24 +357,25 @@ impl Codegen for ExternFunctionDef {
     type Target = ();
 
     fn codegen(&self, context: Arc<RwLock<CodegenContext>>) -> anyhow::Result<Self::Target> {
-        let func_type = self.return_type.codegen(context.clone())?.function_type(
-            self.params
-                .iter()
-                .map(|param| param.param_type.codegen(context.clone()).unwrap())
-                .collect(),
-            self.span.clone(),
-        )?;
-
         let params = self
             .params
             .iter()
             .map(|param| {
-                (
-                    param.param_type.codegen(context.clone()).unwrap(),
+                Ok((
+                    param.param_type.codegen(context.clone())?,
                     param.name.clone(),
-                )
+                ))
             })
-            .collect::<Vec<_>>();
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        let return_type = self.return_type.codegen(context.clone())?;
+        let func_type = return_type.function_type(
+            params
+                .iter()
+                .map(|(param_type, _)| param_type.clone())
+                .collect(),
+            self.span.clone(),
+        )?;
 
         let mut context_write = context.write();
 
@@ -178,7 +383,7 @@ impl Codegen for ExternFunctionDef {
 
         context_write.c_program.insert_function(CFunction::new(
             name.clone(),
-            self.return_type.codegen(context.clone())?,
+            return_type,
             false,
             true,
             params,
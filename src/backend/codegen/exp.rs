@@ -1,4 +1,5 @@
 use super::*;
+use alloc::string::ToString;
 
 impl Codegen for ConstExp {
     type Target = Value;
@@ -13,68 +14,571 @@ impl Codegen for Exp {
 
     fn codegen(&self, context: Arc<RwLock<CodegenContext>>) -> anyhow::Result<Value> {
         Ok(match self {
-            Exp::Binary(lhs, op, rhs, _span) => {
-                let lhs = lhs.codegen(context.clone())?;
-                let rhs = rhs.codegen(context.clone())?;
-                Value::new_bin_op(BinOp::new(lhs, rhs, op.clone()))
+            Exp::Binary(lhs_exp, op, rhs_exp, _span) => {
+                let lhs = lhs_exp.codegen(context.clone())?;
+                let rhs = rhs_exp.codegen(context.clone())?;
+
+                if let BinaryOp::And | BinaryOp::Or = op {
+                    lhs.get_type().as_bool(lhs_exp.get_span())?;
+                    rhs.get_type().as_bool(rhs_exp.get_span())?;
+                }
+
+                let bin_op = BinOp::new(lhs, rhs, op.clone());
+                bin_op.check(lhs_exp.get_span(), rhs_exp.get_span())?;
+
+                Value::new_bin_op(bin_op)
+            }
+            Exp::Unary(op, exp, _span) => {
+                let value = match (op, exp.as_ref()) {
+                    (UnaryOp::Negative, Exp::Number(number)) => number.value(true)?,
+                    _ => exp.codegen(context.clone())?,
+                };
+        
+                let type_ = value.get_type();
+                let valid = match op {
+                    UnaryOp::Not => {
+                        type_.as_bool(exp.get_span())?;
+                        true
+                    }
+                    UnaryOp::Positive | UnaryOp::Negative => type_.is_int() || type_.is_float(),
+                    UnaryOp::BitNot => type_.is_int(),
+                };
+                if !valid {
+                    return Err(CompileError::new_invalid_operand(
+                        exp.get_span(),
+                        op.to_string(),
+                        value.get_type().to_string(),
+                    )
+                    .into());
+                }
+
+                let value = Value::new_unary_op(UnOp::new(value, op.clone()));
+                // A negated literal has to fit its own type once negated, as
+                // in `-1u8` and `-128i8`.
+                value.check_int_literal(&value.get_type(), exp.get_span())?;
+                value
+            }
+            Exp::Number(number) => number.codegen(context.clone())?,
+            // Literals too large for `f64` parse as infinity, which C cannot
+            // spell.
+            Exp::Float(float) if !float.num.is_finite() => {
+                return Err(CompileError::new_literal_overflow(
+                    float.span.clone(),
+                    float.text.clone(),
+                    "f64".into(),
+                )
+                .into());
+            }
+            Exp::Float(float) => Value::new_float(float.num, 64),
+            Exp::Bool(value, _span) => Value::new_bool(*value),
+            Exp::Char(char) if char.unicode => {
+                if char::from_u32(char.num).is_none() {
+                    return Err(
+                        CompileError::new_invalid_code_point(char.span.clone(), char.num).into(),
+                    );
+                }
+                Value::new_int(char.num as u64, 32, false)
+            }
+            Exp::Char(char) => Value::new_int(char.num as u64, 8, false),
+            Exp::Str(str) => {
+                if let Some(code) = str.invalid {
+                    return Err(CompileError::new_invalid_code_point(str.span.clone(), code).into());
+                }
+                Value::new_str(str.value.clone())
             }
-            Exp::Number(number) => Value::new_int(number.num, 64, true),
             Exp::ConvertType(convert_type) => convert_type.codegen(context.clone())?,
             Exp::LVal(lval) => lval.codegen(context.clone())?,
             Exp::Call(call) => call.codegen(context.clone())?,
-            _ => unimplemented!(),
+            Exp::Struct(struct_literal) => struct_literal.codegen(context.clone())?,
+            Exp::Array(array) => array.codegen(context.clone())?,
+            Exp::Deref(deref) => deref.codegen(context.clone())?,
+            Exp::Slice(slice) => slice.codegen(context.clone())?,
+            Exp::GetAddr(get_addr) => get_addr.codegen(context.clone())?,
+            Exp::Exp(exp, _span) => exp.codegen(context.clone())?,
         })
     }
 }
 
+impl Codegen for Number {
+    type Target = Value;
+
+    fn codegen(&self, _context: Arc<RwLock<CodegenContext>>) -> anyhow::Result<Value> {
+        self.value(false)
+    }
+}
+
+impl Number {
+    // A `negated` signed literal may be one past `max()`, so that `min()` can
+    // be written.
+    fn value(&self, negated: bool) -> anyhow::Result<Value> {
+        let (int_type, limit) = match &self.suffix {
+            Some(suffix) => {
+                let int_type = CIntType::new(suffix.bit_width, suffix.signed);
+                (int_type.clone(), int_type)
+            }
+            // Unsuffixed literals can still initialize a `u64`.
+            None => (CIntType::new(64, true), CIntType::new(64, false)),
+        };
+        let max = limit.max() + (negated && limit.signed()) as i128;
+        if self.num > max as u128 {
+            return Err(CompileError::new_literal_overflow(
+                self.span.clone(),
+                self.num.to_string(),
+                CType::new_int(limit).to_string(),
+            )
+            .into());
+        }
+
+        Ok(Value::new_int(
+            self.num as u64,
+            int_type.bit_width(),
+            int_type.signed(),
+        ))
+    }
+}
+
 impl Codegen for Call {
     type Target = Value;
 
     fn codegen(&self, context: Arc<RwLock<CodegenContext>>) -> anyhow::Result<Value> {
         let value = self.exp.codegen(context.clone())?;
-        
-        let call = Value::new_call(CCall::new(
-            value,
-            self.args
-                .iter()
-                .map(|arg| arg.codegen(context.clone()).unwrap())
-                .collect(),
-        ));
+        let function = value.get_type().as_function(self.exp.get_span())?;
+        let param_types = function.get_param_types();
+        if param_types.len() != self.args.len() {
+            return Err(CompileError::new_argument_count(
+                self.span.clone(),
+                param_types.len(),
+                self.args.len(),
+            )
+            .into());
+        }
+
+        let args = self
+            .args
+            .iter()
+            .zip(param_types.iter())
+            .map(|(arg_exp, param_type)| {
+                let arg = arg_exp
+                    .codegen(context.clone())?
+                    .into_compound_literal(param_type.clone())
+                    .coerce(param_type, arg_exp.get_span())?;
+                param_type.check_assignable(&arg.get_type(), arg_exp.get_span())?;
+                Ok(arg)
+            })
+            .collect::<anyhow::Result<_>>()?;
+
+        let call = Value::new_call(CCall::new(value, args, function.get_return_type()));
         Ok(call)
     }
 }
 
+impl Exp {
+    // The variable a field or element access starts at. It is only the
+    // variable being accessed if the generated value `is_direct`.
+    pub fn root(&self) -> Option<&LVal> {
+        match self {
+            Exp::LVal(lval) => match &lval.exp {
+                Some(exp) => exp.root(),
+                None => Some(lval),
+            },
+            Exp::Deref(deref) => match deref.as_ref() {
+                Deref::DerefPtrExp(exp, _, _) => exp.root(),
+                _ => None,
+            },
+            Exp::Slice(slice) => slice.value.root(),
+            _ => None,
+        }
+    }
+}
+
 impl Codegen for LVal {
     type Target = Value;
 
     fn codegen(&self, context: Arc<RwLock<CodegenContext>>) -> anyhow::Result<Value> {
-        let id = self.ids[0].clone();
-        let context = context.read();
-        let value = match context.local.get(&id) {
-            Some(symbol) => match symbol {
-                Symbol::Const(_, value) => value.clone(),
-                Symbol::Var(_, value) => value.clone(),
-                _ => unimplemented!(),
-            },
-            None => match context.global.get(&id) {
-                Some(symbol) => match symbol {
-                    Symbol::Const(_, value) => value.clone(),
-                    Symbol::Var(_, value) => value.clone(),
-                    _ => unimplemented!(),
-                },
-                None => unimplemented!(),
-            },
+        // A path either starts at a symbol or continues an arbitrary
+        // expression, as in `a[i].x`.
+        let (mut value, fields) = match &self.exp {
+            Some(exp) => (exp.codegen(context.clone())?, &self.ids[..]),
+            None => {
+                let id = self.ids[0].clone();
+                let value = match context.read().get_symbol(&id) {
+                    Some(Symbol::Const(_, value)) => value.clone(),
+                    Some(Symbol::Var(_, value)) => value.clone(),
+                    _ => {
+                        return Err(
+                            CompileError::new_symbol_not_found(self.span.clone(), id).into()
+                        );
+                    }
+                };
+                (value, &self.ids[1..])
+            }
         };
+
+        for field in fields.iter() {
+            if let Ok(type_) = value.as_type(self.span.clone()) {
+                value = type_.variant(field, self.span.clone())?;
+                continue;
+            }
+
+            let field_type = value.get_type().field(field, self.span.clone())?;
+            value = Value::new_member(CMember::new(value, field.clone(), field_type));
+        }
+
         Ok(value)
     }
 }
 
+impl Codegen for StructLiteral {
+    type Target = Value;
+
+    fn codegen(&self, context: Arc<RwLock<CodegenContext>>) -> anyhow::Result<Value> {
+        let type_ = context
+            .read()
+            .get_path(&self.ids, self.span.clone())?
+            .as_type(self.span.clone())?;
+
+        if type_.is_union() {
+            return self.codegen_union(type_, context);
+        }
+
+        let mut fields: Vec<(String, Value)> = Vec::new();
+        for field in self.fields.iter() {
+            let field_type = type_.field(&field.name, field.span.clone())?;
+            if fields.iter().any(|(name, _)| *name == field.name) {
+                return Err(CompileError::new_duplicate_field(
+                    field.span.clone(),
+                    field.name.clone(),
+                )
+                .into());
+            }
+
+            let value = field
+                .value
+                .codegen(context.clone())?
+                .coerce(&field_type, field.value.get_span())?;
+            field_type.check_assignable(&value.get_type(), field.value.get_span())?;
+            value.check_initializer(field.value.get_span())?;
+            fields.push((field.name.clone(), value));
+        }
+
+        let missing = type_
+            .fields()
+            .into_iter()
+            .filter(|(name, _)| fields.iter().all(|(field, _)| field != name))
+            .map(|(name, _)| alloc::format!("`{}`", name))
+            .collect::<Vec<_>>();
+        if !missing.is_empty() {
+            return Err(CompileError::new_missing_fields(
+                self.span.clone(),
+                type_.get_name(),
+                missing.join(", "),
+            )
+            .into());
+        }
+
+        Ok(Value::new_struct(CStructLiteral::new(type_, fields)))
+    }
+}
+
+impl StructLiteral {
+    fn codegen_union(
+        &self,
+        union_type: CType,
+        context: Arc<RwLock<CodegenContext>>,
+    ) -> anyhow::Result<Value> {
+        let [field] = self.fields.as_slice() else {
+            return Err(CompileError::new_invalid_union_init(
+                self.span.clone(),
+                union_type.get_name(),
+            )
+            .into());
+        };
+        let Some((tag, payload)) = union_type.union_variant(&field.name) else {
+            return Err(CompileError::new_no_variant(
+                field.span.clone(),
+                union_type.get_name(),
+                field.name.clone(),
+            )
+            .into());
+        };
+
+        let value = field
+            .value
+            .codegen(context)?
+            .coerce(&payload, field.value.get_span())?;
+        payload.check_assignable(&value.get_type(), field.value.get_span())?;
+        value.check_initializer(field.value.get_span())?;
+
+        Ok(Value::new_struct(CStructLiteral::new(
+            union_type,
+            alloc::vec![
+                (UNION_TAG.into(), Value::new_int(tag, 32, false)),
+                (field.name.clone(), value),
+            ],
+        )))
+    }
+}
+
 impl Codegen for ConvertType {
     type Target = Value;
 
     fn codegen(&self, context: Arc<RwLock<CodegenContext>>) -> anyhow::Result<Self::Target> {
         let value = self.exp.codegen(context.clone())?;
         let ty = self.ty.codegen(context)?;
+        value.get_type().check_cast(&ty, self.span.clone())?;
         Ok(Value::new_convert_type(CConvertType::new(value, ty)))
     }
 }
+
+impl Codegen for Array {
+    type Target = Value;
+
+    fn codegen(&self, context: Arc<RwLock<CodegenContext>>) -> anyhow::Result<Value> {
+        match self {
+            Array::List(items, _span) => {
+                let values = items
+                    .iter()
+                    .map(|item| {
+                        let value = item.codegen(context.clone())?;
+                        value.check_initializer(item.get_span())?;
+                        Ok(value)
+                    })
+                    .collect::<anyhow::Result<Vec<_>>>()?;
+
+                let element_type = values[0].get_type();
+                for (item, value) in items.iter().zip(values.iter()).skip(1) {
+                    element_type.check_assignable(&value.get_type(), item.get_span())?;
+                }
+
+                Ok(Value::new_array(CArray::new_list(values, element_type)))
+            }
+            Array::Template(item, len, _span) => {
+                let mut value = item.codegen(context.clone())?;
+                value.check_initializer(item.get_span())?;
+                let len = len.codegen(context.clone())?.as_int(len.exp.get_span())?;
+
+                // The item is written out once per element, so it is
+                // evaluated into a temporary unless that cannot change it.
+                let mut context_write = context.write();
+                if context_write.current_function.is_some()
+                    && !value.is_literal()
+                    && !value.is_array_literal()
+                {
+                    let type_ = value.get_type();
+                    let temp =
+                        CIdentifier::new(context_write.new_temp_name("repeat"), type_.clone());
+                    context_write.insert_block_item(CBlockItem::Decl(CDeclaration::VariableDef(
+                        CVariable::new(temp.get_name(), value, type_),
+                    )));
+                    value = Value::new_identifier(temp);
+                }
+
+                Ok(Value::new_array(CArray::new_repeat(value, len)))
+            }
+        }
+    }
+}
+
+impl Codegen for Deref {
+    type Target = Value;
+
+    fn codegen(&self, context: Arc<RwLock<CodegenContext>>) -> anyhow::Result<Value> {
+        match self {
+            Deref::DerefId(lval, span) => {
+                let value = lval.codegen(context)?;
+                Self::deref(value, span.clone())
+            }
+            Deref::DerefExp(exp, span) => {
+                let value = exp.codegen(context)?;
+                Self::deref(value, span.clone())
+            }
+            Deref::DerefPtr(lval, index_exp, span) => {
+                let value = lval.codegen(context.clone())?;
+                Self::index(value, lval.span.clone(), index_exp, span.clone(), context)
+            }
+            Deref::DerefPtrExp(exp, index_exp, span) => {
+                let value = exp.codegen(context.clone())?;
+                Self::index(value, exp.get_span(), index_exp, span.clone(), context)
+            }
+        }
+    }
+}
+
+impl Deref {
+    fn deref(value: Value, span: Span) -> anyhow::Result<Value> {
+        let pointee = value.get_type().as_pointer(span.clone())?.get_pointee();
+        if pointee.is_void() {
+            return Err(CompileError::new_invalid_operand(
+                span,
+                "*".into(),
+                value.get_type().get_name(),
+            )
+            .into());
+        }
+
+        Ok(Value::new_deref(CDeref::new(value, pointee)))
+    }
+
+    fn index(
+        value: Value,
+        value_span: Span,
+        index_exp: &Exp,
+        span: Span,
+        context: Arc<RwLock<CodegenContext>>,
+    ) -> anyhow::Result<Value> {
+        let index = index_exp.codegen(context)?;
+        index.get_type().as_int(index_exp.get_span())?;
+
+        if let Ok(pointer_type) = value.get_type().as_pointer(value_span.clone()) {
+            let pointee = pointer_type.get_pointee();
+            if pointee.is_void() {
+                return Err(CompileError::new_invalid_operand(
+                    span,
+                    "[]".into(),
+                    value.get_type().get_name(),
+                )
+                .into());
+            }
+            return Ok(Value::new_index(CIndex::new(value, index, pointee)));
+        }
+
+        if let Ok(slice_type) = value.get_type().as_slice(value_span.clone()) {
+            let element_type = slice_type.get_element_type();
+            return Ok(Value::new_slice_index(CSliceIndex::new(
+                slice_type,
+                value,
+                index,
+                element_type,
+            )));
+        }
+
+        let array_type = value.get_type().as_array(value_span)?;
+        if let Ok(n) = index.as_int(index_exp.get_span())
+            && n >= array_type.len()
+        {
+            return Err(CompileError::new_index_out_of_bounds(span, n, array_type.len()).into());
+        }
+
+        let value_type = value.get_type();
+        let value = value.into_compound_literal(value_type);
+        Ok(Value::new_index(CIndex::new(
+            value,
+            index,
+            array_type.get_element_type(),
+        )))
+    }
+}
+
+impl Codegen for GetAddr {
+    type Target = Value;
+
+    fn codegen(&self, context: Arc<RwLock<CodegenContext>>) -> anyhow::Result<Value> {
+        let value = self.exp.codegen(context.clone())?;
+        if !value.is_lvalue() {
+            return Err(CompileError::new_invalid_operand(
+                self.span.clone(),
+                "&".into(),
+                value.get_type().get_name(),
+            )
+            .into());
+        }
+
+        let is_const = match self.exp.root().filter(|_| value.is_direct()) {
+            Some(root) => matches!(
+                context.read().get_path_symbol(&root.ids, root.span.clone()),
+                Some((_, Symbol::Const(..)))
+            ),
+            None => false,
+        };
+        let pointee = value.get_type();
+        let address = Value::new_get_addr(CGetAddr::new(value));
+
+        // Constants can only be read through the pointer.
+        if is_const && !pointee.is_const() {
+            let pointer = CType::new_pointer(CPointerType::new(CType::new_const(pointee)));
+            return Ok(Value::new_convert_type(CConvertType::new(address, pointer)));
+        }
+
+        Ok(address)
+    }
+}
+
+impl Codegen for Slice {
+    type Target = Value;
+
+    fn codegen(&self, context: Arc<RwLock<CodegenContext>>) -> anyhow::Result<Value> {
+        let value = self.value.codegen(context.clone())?;
+        let start = self.range.start.codegen(context.clone())?;
+        let end = self.range.end.codegen(context.clone())?;
+        start.get_type().as_int(self.range.start.get_span())?;
+        end.get_type().as_int(self.range.end.get_span())?;
+
+        let bounds = (
+            start.as_int(self.range.start.get_span()),
+            end.as_int(self.range.end.get_span()),
+        );
+        if let (Ok(start), Ok(end)) = bounds
+            && start > end
+        {
+            return Err(CompileError::new_reversed_slice(self.span.clone(), start, end).into());
+        }
+
+        if let Ok(slice_type) = value.get_type().as_slice(self.value.get_span()) {
+            return Ok(Value::new_sub_slice(CSubSlice::new(
+                slice_type, value, start, end,
+            )));
+        }
+
+        let value_type = value.get_type();
+        let array_type = value_type.as_array(self.value.get_span())?;
+        if let Ok(end) = bounds.1
+            && end > array_type.len()
+        {
+            return Err(CompileError::new_index_out_of_bounds(
+                self.range.end.get_span(),
+                end,
+                array_type.len(),
+            )
+            .into());
+        }
+
+        // Elements of a constant array stay constant behind the slice.
+        let mut element_type = array_type.get_element_type();
+        if value_type.is_const() {
+            element_type = CType::new_const(element_type);
+        }
+        let slice = context.write().new_slice_type(element_type.clone());
+        let value = value.into_compound_literal(value_type);
+
+        // Constant bounds were checked above, and the literal stays usable in
+        // global initializers.
+        if let (Ok(start), Ok(end)) = bounds {
+            let first = CIndex::new(value, Value::new_int(start, 64, false), element_type);
+            return Ok(Value::new_struct(CStructLiteral::new(
+                slice,
+                alloc::vec![
+                    (
+                        "ptr".into(),
+                        Value::new_get_addr(CGetAddr::new(Value::new_index(first))),
+                    ),
+                    ("len".into(), Value::new_int(end - start, 64, false)),
+                ],
+            )));
+        }
+
+        let whole = Value::new_struct(CStructLiteral::new(
+            slice.clone(),
+            alloc::vec![
+                ("ptr".into(), value),
+                ("len".into(), Value::new_int(array_type.len(), 64, false),),
+            ],
+        ));
+
+        Ok(Value::new_sub_slice(CSubSlice::new(
+            slice.as_slice(self.span.clone())?,
+            whole,
+            start,
+            end,
+        )))
+    }
+}
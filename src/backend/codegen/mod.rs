@@ -1,5 +1,5 @@
 use super::*;
-use alloc::vec::Vec;
+use alloc::{boxed::Box, vec::Vec};
 
 mod decl;
 mod exp;
@@ -6,7 +6,11 @@ impl Codegen for CompUnit {
     fn codegen(&self, context: Arc<RwLock<CodegenContext>>) -> anyhow::Result<()> {
         for global_item in self.global_items.iter() {
             let ret = match global_item {
-                GlobalItem::Decl(decl) => decl.codegen(context.clone()),
+                GlobalItem::Decl(decl) => decl.codegen(context.clone()).map(|()| {
+                    if decl.is_pub() {
+                        context.write().export(decl.name());
+                    }
+                }),
             };
             if let Err(e) = ret {
                 let e: CompileError = e.downcast()?;
@@ -18,11 +22,52 @@ impl Codegen for CompUnit {
     }
 }
 
+impl Codegen for Module {
+    type Target = ();
+
+    fn codegen(&self, context: Arc<RwLock<CodegenContext>>) -> anyhow::Result<()> {
+        {
+            let mut context = context.write();
+            context.prefix = self.prefix.clone();
+            context.imports = self.imports.clone();
+        }
+
+        self.ast.codegen(context.clone())?;
+
+        // Later modules only see this one through its public declarations.
+        let mut context = context.write();
+        context.global = SymbolTable::new();
+        let exports = core::mem::replace(&mut context.exports, SymbolTable::new());
+        context.modules.push(CType::new_module(
+            CModuleType::new(self.name.clone()),
+            exports,
+        ));
+
+        Ok(())
+    }
+}
+
 impl Codegen for Type {
     type Target = CType;
 
-    fn codegen(&self, _context: Arc<RwLock<CodegenContext>>) -> anyhow::Result<CType> {
-        match self.ty {
+    fn codegen(&self, context: Arc<RwLock<CodegenContext>>) -> anyhow::Result<CType> {
+        let mut type_ = self.codegen_base(context)?;
+        for star in self.star.iter().rev() {
+            if star.is_const && !type_.is_const() {
+                type_ = CType::new_const(type_);
+            }
+            let mut pointer_type = CPointerType::new(type_);
+            pointer_type.set_volatile(star.is_volatile);
+            type_ = CType::new_pointer(pointer_type);
+        }
+
+        Ok(type_)
+    }
+}
+
+impl Type {
+    fn codegen_base(&self, context: Arc<RwLock<CodegenContext>>) -> anyhow::Result<CType> {
+        match &self.ty {
             TypeEnum::U64 => Ok(CType::new_int(CIntType::new(64, false))),
             TypeEnum::U32 => Ok(CType::new_int(CIntType::new(32, false))),
             TypeEnum::U16 => Ok(CType::new_int(CIntType::new(16, false))),
@@ -31,7 +76,26 @@ impl Codegen for Type {
             TypeEnum::I32 => Ok(CType::new_int(CIntType::new(32, true))),
             TypeEnum::I16 => Ok(CType::new_int(CIntType::new(16, true))),
             TypeEnum::I8 => Ok(CType::new_int(CIntType::new(8, true))),
+            TypeEnum::F64 => Ok(CType::new_float(CFloatType::new(64))),
+            TypeEnum::F32 => Ok(CType::new_float(CFloatType::new(32))),
+            TypeEnum::Bool => Ok(CType::new_bool()),
             TypeEnum::Void => Ok(CType::new_void()),
+            TypeEnum::Named(path) => context
+                .read()
+                .get_path(path, self.span.clone())?
+                .as_type(self.span.clone()),
+            TypeEnum::Array(element_type, len) => {
+                let element_type = element_type.codegen(context.clone())?;
+                let len = len.codegen(context)?.as_int(len.exp.get_span())?;
+                Ok(CType::new_array(CArrayType::new(element_type, len)))
+            }
+            TypeEnum::Slice(element_type, is_const) => {
+                let mut element_type = element_type.codegen(context.clone())?;
+                if *is_const {
+                    element_type = CType::new_const(element_type);
+                }
+                Ok(context.write().new_slice_type(element_type))
+            }
         }
     }
 }
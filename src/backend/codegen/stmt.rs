@@ -1,15 +1,19 @@
 use super::*;
 
 impl Codegen for Block {
-    type Target = ();
+    type Target = CBlock;
 
-    fn codegen(&self, context: Arc<RwLock<CodegenContext>>) -> anyhow::Result<()> {
-        context.write().push_scope();
-        let stack_size = context.read().local.len();
+    fn codegen(&self, context: Arc<RwLock<CodegenContext>>) -> anyhow::Result<CBlock> {
+        let mut context_write = context.write();
+        context_write.push_scope();
+        context_write.push_block();
+        let stack_size = context_write.local.len();
+        drop(context_write);
 
-        for item in self.items.iter() {
-            item.codegen(context.clone())?;
-        }
+        let result = self
+            .items
+            .iter()
+            .try_for_each(|item| item.codegen(context.clone()));
 
         let mut context_write = context.write();
 
@@ -18,8 +22,9 @@ impl Codegen for Block {
         }
 
         context_write.pop_scope();
+        let block = context_write.pop_block();
 
-        Ok(())
+        result.map(|_| block)
     }
 }
 
@@ -29,6 +34,9 @@ impl Codegen for BlockItem {
     fn codegen(&self, context: Arc<RwLock<CodegenContext>>) -> anyhow::Result<()> {
         match self {
             BlockItem::Statement(statement) => statement.codegen(context.clone())?,
+            BlockItem::Declaration(decl) if decl.is_pub() => {
+                return Err(CompileError::new_local_pub(decl.get_span()).into());
+            }
             BlockItem::Declaration(decl) => decl.codegen(context.clone())?,
         }
 
@@ -45,55 +53,729 @@ impl Codegen for Statement {
             Self::Exp(exp) => {
                 if let Some(exp) = exp {
                     let value = exp.codegen(context.clone())?;
-                    let mut context_write = context.write();
-                    let current = context_write.current_function.clone().unwrap().get_name();
-                    let current = context_write.c_program.function_mut(current).unwrap();
-                    current.body.push(CBlockItem::Exp(Some(value.clone())));
+                    context
+                        .write()
+                        .insert_block_item(CBlockItem::Exp(Some(value.clone())));
                 }
             }
+            Statement::If(r#if) => {
+                let r#if = r#if.codegen(context.clone())?;
+                context
+                    .write()
+                    .insert_block_item(CBlockItem::Statement(CStatement::If(r#if)));
+            }
+            Statement::While(r#while) => {
+                let r#while = r#while.codegen(context.clone())?;
+                context
+                    .write()
+                    .insert_block_item(CBlockItem::Statement(CStatement::While(r#while)));
+            }
+            Statement::For(r#for) => {
+                let r#for = r#for.codegen(context.clone())?;
+                context
+                    .write()
+                    .insert_block_item(CBlockItem::Statement(CStatement::For(Box::new(r#for))));
+            }
+            Statement::Match(r#match) => {
+                let switch = r#match.codegen(context.clone())?;
+                context
+                    .write()
+                    .insert_block_item(CBlockItem::Statement(CStatement::Switch(switch)));
+            }
+            Statement::Switch(switch) => {
+                let switch = switch.codegen(context.clone())?;
+                context
+                    .write()
+                    .insert_block_item(CBlockItem::Statement(CStatement::Switch(switch)));
+            }
+            Statement::Assign(assign) => {
+                let assign = assign.codegen(context.clone())?;
+                context
+                    .write()
+                    .insert_block_item(CBlockItem::Statement(CStatement::Assign(assign)));
+            }
+            Statement::Break(r#break) => {
+                let r#break = r#break.codegen(context.clone())?;
+                context
+                    .write()
+                    .insert_block_item(CBlockItem::Statement(r#break));
+            }
+            Statement::Continue(r#continue) => {
+                let r#continue = r#continue.codegen(context.clone())?;
+                context
+                    .write()
+                    .insert_block_item(CBlockItem::Statement(r#continue));
+            }
         }
 
         Ok(())
     }
 }
 
+impl Codegen for If {
+    type Target = CIf;
+
+    fn codegen(&self, context: Arc<RwLock<CodegenContext>>) -> anyhow::Result<CIf> {
+        let condition = self.condition.codegen(context.clone())?;
+        condition.get_type().as_bool(self.condition.get_span())?;
+        let then_block = self.then_block.codegen(context.clone())?;
+
+        let else_block = match &self.else_block {
+            Some(Else::If(r#if)) => {
+                let mut block = CBlock::new();
+                block.push(CBlockItem::Statement(CStatement::If(
+                    r#if.codegen(context.clone())?,
+                )));
+                Some(block)
+            }
+            Some(Else::Block(block)) => Some(block.codegen(context.clone())?),
+            None => None,
+        };
+
+        Ok(CIf::new(condition, then_block, else_block))
+    }
+}
+
+impl Codegen for While {
+    type Target = CWhile;
+
+    fn codegen(&self, context: Arc<RwLock<CodegenContext>>) -> anyhow::Result<CWhile> {
+        let condition = self.condition.codegen(context.clone())?;
+        condition.get_type().as_bool(self.condition.get_span())?;
+
+        context
+            .write()
+            .push_loop(self.label.as_ref().map(|label| label.name.clone()));
+        let body = self.block.codegen(context.clone());
+        let r#loop = context.write().pop_loop();
+
+        let mut r#while = CWhile::new(condition, body?);
+        if r#loop.break_used {
+            r#while.set_break_label(r#loop.break_label());
+        }
+        if r#loop.continue_used {
+            r#while.set_continue_label(r#loop.continue_label());
+        }
+
+        Ok(r#while)
+    }
+}
+
+impl Codegen for For {
+    type Target = CFor;
+
+    fn codegen(&self, context: Arc<RwLock<CodegenContext>>) -> anyhow::Result<CFor> {
+        match &self.iter {
+            ForIter::Range(range) => self.codegen_range(range, context),
+            ForIter::Array(exp) => self.codegen_array(exp, context),
+        }
+    }
+}
+
+impl For {
+    fn codegen_range(
+        &self,
+        range: &Range,
+        context: Arc<RwLock<CodegenContext>>,
+    ) -> anyhow::Result<CFor> {
+        let start = range.start.codegen(context.clone())?;
+        let end = range.end.codegen(context.clone())?;
+        start
+            .get_type()
+            .check_assignable(&end.get_type(), range.end.get_span())?;
+
+        // An unsuffixed integer literal bound takes the type of the other
+        // bound, and both have to fit the loop variable.
+        let var_type = if Self::is_untyped_literal(&range.start) {
+            end.get_type()
+        } else {
+            start.get_type()
+        };
+        var_type.as_int(range.span.clone())?;
+        let start = start.coerce(&var_type, range.start.get_span())?;
+        let end = end.coerce(&var_type, range.end.get_span())?;
+
+        let var = CIdentifier::new(self.var.clone(), var_type.clone());
+        self.codegen_loop(var, start, end, Value::new_identifier, context)
+    }
+
+    fn is_untyped_literal(exp: &Exp) -> bool {
+        match exp {
+            Exp::Number(number) => number.suffix.is_none(),
+            Exp::Unary(UnaryOp::Negative, exp, _) => Self::is_untyped_literal(exp),
+            _ => false,
+        }
+    }
+
+    fn codegen_array(
+        &self,
+        exp: &Exp,
+        context: Arc<RwLock<CodegenContext>>,
+    ) -> anyhow::Result<CFor> {
+        let mut array = exp.codegen(context.clone())?;
+        let array_type = array.get_type();
+        if array_type.is_slice() {
+            return self.codegen_slice(array, context);
+        }
+        let element_type = array_type.as_array(exp.get_span())?.get_element_type();
+
+        // Literals are stored once instead of being rebuilt on every iteration.
+        if array.is_array_literal() {
+            let mut context_write = context.write();
+            let temp = CIdentifier::new(context_write.new_temp_name("for"), array_type.clone());
+            context_write.insert_block_item(CBlockItem::Decl(CDeclaration::VariableDef(
+                CVariable::new(temp.get_name(), array, array_type.clone()),
+            )));
+            array = Value::new_identifier(temp);
+        }
+
+        let index_type = CType::new_int(CIntType::new(64, false));
+        let index = CIdentifier::new(context.write().new_temp_name("index"), index_type);
+        let len = Value::new_int(array_type.as_array(exp.get_span())?.len(), 64, false);
+
+        self.codegen_loop(
+            index,
+            Value::new_int(0, 64, false),
+            len,
+            |index| {
+                Value::new_index(CIndex::new(
+                    array.clone(),
+                    Value::new_identifier(index),
+                    element_type.clone(),
+                ))
+            },
+            context,
+        )
+    }
+
+    // The slice is copied once, so the loop sees a fixed pointer and length.
+    fn codegen_slice(
+        &self,
+        slice: Value,
+        context: Arc<RwLock<CodegenContext>>,
+    ) -> anyhow::Result<CFor> {
+        let slice_type = slice.get_type();
+        let index_type = CType::new_int(CIntType::new(64, false));
+
+        let mut context_write = context.write();
+        let temp = CIdentifier::new(context_write.new_temp_name("for"), slice_type.clone());
+        context_write.insert_block_item(CBlockItem::Decl(CDeclaration::VariableDef(
+            CVariable::new(temp.get_name(), slice, slice_type.clone()),
+        )));
+        let index_name = context_write.new_temp_name("index");
+        drop(context_write);
+
+        let field = |name: &str| {
+            let type_ = slice_type.field(name, self.span.clone())?;
+            anyhow::Ok(Value::new_member(CMember::new(
+                Value::new_identifier(temp.clone()),
+                name.into(),
+                type_,
+            )))
+        };
+        let ptr = field("ptr")?;
+        let len = field("len")?;
+        let element_type = slice_type.as_slice(self.span.clone())?.get_element_type();
+
+        self.codegen_loop(
+            CIdentifier::new(index_name, index_type),
+            Value::new_int(0, 64, false),
+            len,
+            |index| Value::new_index(CIndex::new(ptr, Value::new_identifier(index), element_type)),
+            context,
+        )
+    }
+
+    // `bind` turns the loop counter into the value the loop variable names.
+    fn codegen_loop(
+        &self,
+        var: CIdentifier,
+        start: Value,
+        end: Value,
+        bind: impl FnOnce(CIdentifier) -> Value,
+        context: Arc<RwLock<CodegenContext>>,
+    ) -> anyhow::Result<CFor> {
+        let var_type = var.get_type();
+
+        let mut context_write = context.write();
+        context_write.push_loop(self.label.as_ref().map(|label| label.name.clone()));
+        context_write.push_scope();
+        context_write
+            .local
+            .push(Symbol::Const(self.var.clone(), bind(var.clone())));
+        drop(context_write);
+
+        let body = self.block.codegen(context.clone());
+
+        let mut context_write = context.write();
+        context_write.local.pop();
+        context_write.pop_scope();
+        let r#loop = context_write.pop_loop();
+
+        let end_var = CIdentifier::new(r#loop.end_name(), var_type);
+        let mut r#for = CFor::new(var, start, end_var, end, body?);
+        if r#loop.break_used {
+            r#for.set_break_label(r#loop.break_label());
+        }
+        if r#loop.continue_used {
+            r#for.set_continue_label(r#loop.continue_label());
+        }
+
+        Ok(r#for)
+    }
+}
+
+impl Codegen for Match {
+    type Target = CSwitch;
+
+    fn codegen(&self, context: Arc<RwLock<CodegenContext>>) -> anyhow::Result<CSwitch> {
+        let value = self.value.codegen(context.clone())?;
+        let union_type = value.get_type();
+        if !union_type.is_union() {
+            return Err(CompileError::new_mismatched_type(
+                self.value.get_span(),
+                "union".into(),
+                union_type.get_name(),
+            )
+            .into());
+        }
+
+        // The matched value is evaluated once and payloads are read from the
+        // copy.
+        let mut context_write = context.write();
+        let temp = CIdentifier::new(context_write.new_temp_name("match"), union_type.clone());
+        context_write.insert_block_item(CBlockItem::Decl(CDeclaration::VariableDef(
+            CVariable::new(temp.get_name(), value, union_type.clone()),
+        )));
+        context_write.push_switch();
+        drop(context_write);
+
+        let cases = self.codegen_arms(&temp, context.clone());
+        let default = self
+            .else_block
+            .as_ref()
+            .map(|block| block.codegen(context.clone()))
+            .transpose();
+
+        context.write().pop_switch();
+
+        let tag = CMember::new(
+            Value::new_identifier(temp),
+            UNION_TAG.into(),
+            CType::new_int(CIntType::new(32, false)),
+        );
+        Ok(CSwitch::new(Value::new_member(tag), cases?, default?))
+    }
+}
+
+impl Match {
+    fn codegen_arms(
+        &self,
+        temp: &CIdentifier,
+        context: Arc<RwLock<CodegenContext>>,
+    ) -> anyhow::Result<Vec<CCase>> {
+        let union_type = temp.get_type();
+        let mut matched: Vec<&str> = Vec::new();
+        let mut cases = Vec::new();
+
+        for arm in self.arms.iter() {
+            let Some((tag, payload)) = union_type.union_variant(&arm.variant) else {
+                return Err(CompileError::new_no_variant(
+                    arm.span.clone(),
+                    union_type.get_name(),
+                    arm.variant.clone(),
+                )
+                .into());
+            };
+            if matched.contains(&arm.variant.as_str()) {
+                return Err(
+                    CompileError::new_duplicate_arm(arm.span.clone(), arm.variant.clone()).into(),
+                );
+            }
+            matched.push(&arm.variant);
+
+            let mut context_write = context.write();
+            context_write.push_scope();
+            if let Some(binding) = &arm.binding {
+                if payload.is_void() {
+                    context_write.pop_scope();
+                    return Err(CompileError::new_no_payload(
+                        arm.span.clone(),
+                        union_type.get_name(),
+                        arm.variant.clone(),
+                    )
+                    .into());
+                }
+
+                // The binding reads the payload straight from the copy.
+                let member = CMember::new(
+                    Value::new_identifier(temp.clone()),
+                    arm.variant.clone(),
+                    payload,
+                );
+                context_write
+                    .local
+                    .push(Symbol::Const(binding.clone(), Value::new_member(member)));
+            }
+            drop(context_write);
+
+            let block = arm.block.codegen(context.clone());
+
+            let mut context_write = context.write();
+            if arm.binding.is_some() {
+                context_write.local.pop();
+            }
+            context_write.pop_scope();
+
+            cases.push(CCase::new(
+                alloc::vec![Value::new_int(tag, 32, false)],
+                block?,
+            ));
+        }
+
+        let missing = union_type
+            .fields()
+            .into_iter()
+            .filter(|(variant, _)| !matched.contains(&variant.as_str()))
+            .map(|(variant, _)| alloc::format!("`{}`", variant))
+            .collect::<Vec<_>>();
+        if self.else_block.is_none() && !missing.is_empty() {
+            return Err(
+                CompileError::new_non_exhaustive(self.span.clone(), missing.join(", ")).into(),
+            );
+        }
+
+        Ok(cases)
+    }
+}
+
+impl Codegen for Switch {
+    type Target = CSwitch;
+
+    fn codegen(&self, context: Arc<RwLock<CodegenContext>>) -> anyhow::Result<CSwitch> {
+        let value = self.value.codegen(context.clone())?;
+        let value_type = value.get_type();
+        if !value_type.is_int() && !value_type.is_enum() {
+            return Err(CompileError::new_mismatched_type(
+                self.value.get_span(),
+                "int".into(),
+                value_type.get_name(),
+            )
+            .into());
+        }
+
+        // Ranges are tested in `default`, which has to read the value again.
+        let has_ranges = self.arms.iter().any(|arm| {
+            arm.cases
+                .iter()
+                .any(|case| matches!(case, SwitchCase::Range(_)))
+        });
+        let value = if has_ranges {
+            let mut context_write = context.write();
+            let temp = CIdentifier::new(context_write.new_temp_name("switch"), value_type.clone());
+            context_write.insert_block_item(CBlockItem::Decl(CDeclaration::VariableDef(
+                CVariable::new(temp.get_name(), value, value_type),
+            )));
+            Value::new_identifier(temp)
+        } else {
+            value
+        };
+
+        context.write().push_switch();
+        let arms = self.codegen_arms(&value, context.clone());
+        context.write().pop_switch();
+
+        let (cases, default) = arms?;
+        Ok(CSwitch::new(value, cases, default))
+    }
+}
+
+impl Switch {
+    // Arms made of plain values become case labels. Arms containing a range
+    // are lowered to comparisons chained in `default`.
+    fn codegen_arms(
+        &self,
+        value: &Value,
+        context: Arc<RwLock<CodegenContext>>,
+    ) -> anyhow::Result<(Vec<CCase>, Option<CBlock>)> {
+        let mut seen: Vec<(i128, i128, Span)> = Vec::new();
+        let mut cases = Vec::new();
+        let mut comparisons = Vec::new();
+
+        for arm in self.arms.iter() {
+            let mut values = Vec::new();
+            let mut condition: Option<Value> = None;
+            let has_range = arm
+                .cases
+                .iter()
+                .any(|case| matches!(case, SwitchCase::Range(_)));
+
+            for case in arm.cases.iter() {
+                let (start, end, test, span) = match case {
+                    SwitchCase::Value(exp) => {
+                        let (case_value, n) = Self::case_value(exp, value, context.clone())?;
+                        let test = BinOp::new(value.clone(), case_value.clone(), BinaryOp::Eq);
+                        values.push(case_value);
+                        (n, n + 1, Value::new_bin_op(test), exp.get_span())
+                    }
+                    SwitchCase::Range(range) => {
+                        let (start_value, start) =
+                            Self::case_value(&range.start, value, context.clone())?;
+                        let (end_value, end) =
+                            Self::case_value(&range.end, value, context.clone())?;
+                        if start >= end {
+                            return Err(CompileError::new_empty_range(
+                                range.span.clone(),
+                                alloc::format!("{}..{}", start, end),
+                            )
+                            .into());
+                        }
+
+                        let lower = BinOp::new(value.clone(), start_value, BinaryOp::Ge);
+                        let upper = BinOp::new(value.clone(), end_value, BinaryOp::Lt);
+                        let test = BinOp::new(
+                            Value::new_bin_op(lower),
+                            Value::new_bin_op(upper),
+                            BinaryOp::And,
+                        );
+                        (start, end, Value::new_bin_op(test), range.span.clone())
+                    }
+                };
+
+                if let Some((previous_start, previous_end, previous)) =
+                    seen.iter().find(|(lo, hi, _)| start < *hi && *lo < end)
+                {
+                    let error = if end - start > 1 {
+                        CompileError::new_overlapping_case(
+                            span,
+                            alloc::format!("{}..{}", start, end),
+                        )
+                    } else if (start, end) == (*previous_start, *previous_end) {
+                        CompileError::new_duplicate_case(span, alloc::format!("{}", start))
+                    } else {
+                        CompileError::new_overlapping_case(span, alloc::format!("{}", start))
+                    };
+                    return Err(error
+                        .with_note(previous.clone(), "previous case here".into())
+                        .into());
+                }
+                seen.push((start, end, span));
+
+                condition = Some(match condition {
+                    Some(condition) => Value::new_bin_op(BinOp::new(condition, test, BinaryOp::Or)),
+                    None => test,
+                });
+            }
+
+            let block = arm.block.codegen(context.clone())?;
+            if has_range {
+                comparisons.push((condition.unwrap(), block));
+            } else {
+                cases.push(CCase::new(values, block));
+            }
+        }
+
+        let mut default = self
+            .else_block
+            .as_ref()
+            .map(|block| block.codegen(context.clone()))
+            .transpose()?;
+        for (condition, block) in comparisons.into_iter().rev() {
+            let mut chain = CBlock::new();
+            chain.push(CBlockItem::Statement(CStatement::If(CIf::new(
+                condition, block, default,
+            ))));
+            default = Some(chain);
+        }
+
+        Ok((cases, default))
+    }
+
+    // Case values must be integer constants, optionally negated, or enum
+    // variants of the switched type.
+    fn case_value(
+        exp: &Exp,
+        value: &Value,
+        context: Arc<RwLock<CodegenContext>>,
+    ) -> anyhow::Result<(Value, i128)> {
+        let (case_value, n) = match exp {
+            Exp::Unary(UnaryOp::Negative, inner, _) => {
+                let case_value = inner.codegen(context)?;
+                let n = -(case_value.as_int(exp.get_span())? as i128);
+                (
+                    Value::new_unary_op(UnOp::new(case_value, UnaryOp::Negative)),
+                    n,
+                )
+            }
+            _ => {
+                let case_value = exp.codegen(context)?;
+                let n = case_value.as_int(exp.get_span())? as i128;
+                (case_value, n)
+            }
+        };
+
+        value
+            .get_type()
+            .check_assignable(&case_value.get_type(), exp.get_span())?;
+        case_value.check_int_literal(&value.get_type(), exp.get_span())?;
+
+        Ok((case_value, n))
+    }
+}
+
+impl Codegen for Assign {
+    type Target = CAssign;
+
+    fn codegen(&self, context: Arc<RwLock<CodegenContext>>) -> anyhow::Result<CAssign> {
+        let target = self.target.codegen(context.clone())?;
+        if let Some(root) = self.target.root().filter(|_| target.is_direct()) {
+            let symbol = context.read().get_path_symbol(&root.ids, root.span.clone());
+            if let Some((id, Symbol::Const(..))) = symbol {
+                return Err(CompileError::new_assign_to_const(root.span.clone(), id).into());
+            }
+        }
+        if !target.is_lvalue() || target.get_type().is_const() || target.get_type().is_array() {
+            return Err(CompileError::new_invalid_assign_target(
+                self.target.get_span(),
+                target.get_type().get_name(),
+            )
+            .into());
+        }
+
+        let mut value = self.value.codegen(context.clone())?;
+
+        // `p += n` keeps the pointer type rather than assigning an integer.
+        if let Some(op) = &self.op
+            && target.get_type().is_pointer()
+        {
+            let bin_op = BinOp::new(target.clone(), value.clone(), op.clone());
+            bin_op.check(self.target.get_span(), self.value.get_span())?;
+            target
+                .get_type()
+                .check_assignable(&Value::new_bin_op(bin_op).get_type(), self.value.get_span())?;
+            return Ok(CAssign::new(target, self.op.clone(), value));
+        }
+
+        value = value.coerce(&target.get_type(), self.value.get_span())?;
+        target
+            .get_type()
+            .check_assignable(&value.get_type(), self.value.get_span())?;
+
+        if let Some(op) = &self.op {
+            let bin_op = BinOp::new(target.clone(), value.clone(), op.clone());
+            bin_op.check(self.target.get_span(), self.value.get_span())?;
+
+            // Signed `<<=` goes through the binary operator, which shifts the
+            // unsigned representation.
+            if let (BinaryOp::Shl, Ok(int_type)) =
+                (op, target.get_type().as_int(self.target.get_span()))
+                && int_type.signed()
+            {
+                return Ok(CAssign::new(target, None, Value::new_bin_op(bin_op)));
+            }
+        }
+
+        Ok(CAssign::new(target, self.op.clone(), value))
+    }
+}
+
+impl Codegen for Break {
+    type Target = CStatement;
+
+    fn codegen(&self, context: Arc<RwLock<CodegenContext>>) -> anyhow::Result<CStatement> {
+        let mut context_write = context.write();
+
+        if context_write.loops.is_empty() {
+            return Err(
+                CompileError::new_outside_of_loop(self.span.clone(), "break".into()).into(),
+            );
+        }
+
+        let Some(label) = &self.label else {
+            // Inside a switch C's `break` would only leave the switch.
+            let r#loop = context_write.loops.last_mut().unwrap();
+            if r#loop.switch_depth > 0 {
+                r#loop.break_used = true;
+                return Ok(CStatement::Goto(r#loop.break_label()));
+            }
+            return Ok(CStatement::Break);
+        };
+
+        let Some(r#loop) = context_write.find_loop(&label.name) else {
+            return Err(
+                CompileError::new_undeclared_label(label.span.clone(), label.name.clone()).into(),
+            );
+        };
+
+        r#loop.break_used = true;
+        Ok(CStatement::Goto(r#loop.break_label()))
+    }
+}
+
+impl Codegen for Continue {
+    type Target = CStatement;
+
+    fn codegen(&self, context: Arc<RwLock<CodegenContext>>) -> anyhow::Result<CStatement> {
+        let mut context_write = context.write();
+
+        if context_write.loops.is_empty() {
+            return Err(
+                CompileError::new_outside_of_loop(self.span.clone(), "continue".into()).into(),
+            );
+        }
+
+        let Some(label) = &self.label else {
+            return Ok(CStatement::Continue);
+        };
+
+        let Some(r#loop) = context_write.find_loop(&label.name) else {
+            return Err(
+                CompileError::new_undeclared_label(label.span.clone(), label.name.clone()).into(),
+            );
+        };
+
+        r#loop.continue_used = true;
+        Ok(CStatement::Goto(r#loop.continue_label()))
+    }
+}
+
 impl Codegen for Return {
     type Target = ();
 
     fn codegen(&self, context: Arc<RwLock<CodegenContext>>) -> anyhow::Result<()> {
-        let value = self
+        let mut value = self
             .value
             .as_ref()
-            .map(|value| value.codegen(context.clone()));
+            .map(|value| value.codegen(context.clone()))
+            .transpose()?;
 
-        match value {
-            Some(Ok(value)) => {
-                let function = context.read().current_function.clone().unwrap();
-                let mut context_write = context.write();
-                context_write
-                    .c_program
-                    .function_mut(function.get_name())
-                    .unwrap()
-                    .body
-                    .push(CBlockItem::Statement(CStatement::Return(CReturn::new(
-                        Some(value),
-                    ))));
-            }
-            None => {
-                let function_name = context.read().current_function.clone().unwrap().get_name();
-                context
-                    .write()
-                    .c_program
-                    .function_mut(function_name)
-                    .unwrap()
-                    .body
-                    .push(CBlockItem::Statement(CStatement::Return(CReturn::new(
-                        None,
-                    ))));
+        let function = context.read().current_function.clone().unwrap();
+        let return_type = function
+            .get_type()
+            .as_function(self.span.clone())?
+            .get_return_type();
+        match (&self.value, &mut value) {
+            (Some(exp), Some(value)) => {
+                *value = value.clone().coerce(&return_type, exp.get_span())?;
+                return_type.check_assignable(&value.get_type(), exp.get_span())?;
+            }
+            _ if !return_type.is_void() => {
+                return Err(CompileError::new_mismatched_type(
+                    self.span.clone(),
+                    return_type.get_name(),
+                    "void".into(),
+                )
+                .into());
             }
             _ => {}
         }
 
+        context
+            .write()
+            .insert_block_item(CBlockItem::Statement(CStatement::Return(CReturn::new(
+                value,
+            ))));
+
         Ok(())
     }
 }
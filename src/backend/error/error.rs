@@ -15,6 +15,76 @@ pub enum CompileErrorEnum {
     Terminated,
     #[error("cannot use value of type `{0}` as none-comptime value")]
     NonComptimeValue(String),
+    #[error("`{0}` outside of a loop")]
+    OutsideOfLoop(String),
+    #[error("use of undeclared label `{0}`")]
+    UndeclaredLabel(String),
+    #[error("cannot assign to constant `{0}`")]
+    AssignToConst(String),
+    #[error("mismatched types: expected `{expected}`, found `{found}`")]
+    MismatchedType { expected: String, found: String },
+    #[error("cannot apply operator `{op}` to type `{ty}`")]
+    InvalidOperand { op: String, ty: String },
+    #[error("shift amount `{amount}` is not less than the bit width of `{ty}`")]
+    ShiftOverflow { amount: u64, ty: String },
+    #[error("shift amount `{0}` is negative")]
+    NegativeShift(i128),
+    #[error("expected {expected} arguments, found {found}")]
+    ArgumentCount { expected: usize, found: usize },
+    #[error("cannot assign to a value of type `{0}`")]
+    InvalidAssignTarget(String),
+    #[error("no field `{field}` on type `{ty}`")]
+    NoField { ty: String, field: String },
+    #[error("field `{0}` is already declared")]
+    DuplicateField(String),
+    #[error("missing fields {fields} in initializer of `{ty}`")]
+    MissingFields { ty: String, fields: String },
+    #[error("no variant `{variant}` in `{ty}`")]
+    NoVariant { ty: String, variant: String },
+    #[error("variant `{0}` is already declared")]
+    DuplicateVariant(String),
+    #[error("enum discriminant `{value}` does not fit in `{ty}`")]
+    DiscriminantOverflow { value: i128, ty: String },
+    #[error("discriminant value `{value}` is already assigned to `{variant}`")]
+    DuplicateDiscriminant { value: i128, variant: String },
+    #[error("variant `{variant}` of `{ty}` requires a payload")]
+    MissingPayload { ty: String, variant: String },
+    #[error("variant `{variant}` of `{ty}` has no payload")]
+    NoPayload { ty: String, variant: String },
+    #[error("union `{0}` must be initialized with exactly one variant")]
+    InvalidUnionInit(String),
+    #[error("non-exhaustive match: missing variants {0}")]
+    NonExhaustive(String),
+    #[error("variant `{0}` is already matched")]
+    DuplicateArm(String),
+    #[error("duplicate case `{0}`")]
+    DuplicateCase(String),
+    #[error("case `{0}` overlaps a previous case")]
+    OverlappingCase(String),
+    #[error("range `{0}` is empty")]
+    EmptyRange(String),
+    #[error("index `{index}` is out of bounds for an array of length `{len}`")]
+    IndexOutOfBounds { index: u64, len: u64 },
+    #[error("arrays can only be initialized from array literals")]
+    ArrayCopy,
+    #[error("slice starts at `{start}` but ends at `{end}`")]
+    ReversedSlice { start: u64, end: u64 },
+    #[error("`{0:#x}` is not a unicode code point")]
+    InvalidCodePoint(u32),
+    #[error("literal `{value}` does not fit in `{ty}`")]
+    LiteralOverflow { value: String, ty: String },
+    #[error("module `{module}` has no public declaration `{name}`")]
+    NoExport { module: String, name: String },
+    #[error("cannot find module `{0}`")]
+    ModuleNotFound(String),
+    #[error("import cycle: {0}")]
+    ImportCycle(String),
+    #[error("`@import` is only allowed in top-level declarations")]
+    MisplacedImport,
+    #[error("only top-level declarations can be `pub`")]
+    LocalPub,
+    #[error("cannot infer a variable type from a value of type `{0}`")]
+    CannotInfer(String),
 }
 
 #[derive(Error, Debug)]
@@ -22,13 +92,20 @@ pub struct CompileError {
     pub span: Span,
     #[source]
     pub error: CompileErrorEnum,
+    pub note: Option<(Span, String)>,
 }
 
 impl CompileError {
+    pub fn with_note(mut self, span: Span, note: String) -> Self {
+        self.note = Some((span, note));
+        self
+    }
+
     pub fn new_symbol_not_found(span: Span, id: String) -> Self {
         Self {
             span,
             error: CompileErrorEnum::SymbolNotFound(id),
+            note: None,
         }
     }
 
@@ -36,6 +113,7 @@ impl CompileError {
         Self {
             span,
             error: CompileErrorEnum::InvalidTypeCast { from, to },
+            note: None,
         }
     }
 
@@ -43,6 +121,7 @@ impl CompileError {
         Self {
             span,
             error: CompileErrorEnum::Terminated,
+            note: None,
         }
     }
 
@@ -50,12 +129,300 @@ impl CompileError {
         Self {
             span,
             error: CompileErrorEnum::NonComptimeValue(ty),
+            note: None,
+        }
+    }
+
+    pub fn new_outside_of_loop(span: Span, keyword: String) -> Self {
+        Self {
+            span,
+            error: CompileErrorEnum::OutsideOfLoop(keyword),
+            note: None,
+        }
+    }
+
+    pub fn new_undeclared_label(span: Span, label: String) -> Self {
+        Self {
+            span,
+            error: CompileErrorEnum::UndeclaredLabel(label),
+            note: None,
+        }
+    }
+
+    pub fn new_mismatched_type(span: Span, expected: String, found: String) -> Self {
+        Self {
+            span,
+            error: CompileErrorEnum::MismatchedType { expected, found },
+            note: None,
+        }
+    }
+
+    pub fn new_invalid_operand(span: Span, op: String, ty: String) -> Self {
+        Self {
+            span,
+            error: CompileErrorEnum::InvalidOperand { op, ty },
+            note: None,
+        }
+    }
+
+    pub fn new_shift_overflow(span: Span, amount: u64, ty: String) -> Self {
+        Self {
+            span,
+            error: CompileErrorEnum::ShiftOverflow { amount, ty },
+            note: None,
+        }
+    }
+
+    pub fn new_negative_shift(span: Span, amount: i128) -> Self {
+        Self {
+            span,
+            error: CompileErrorEnum::NegativeShift(amount),
+            note: None,
+        }
+    }
+
+    pub fn new_argument_count(span: Span, expected: usize, found: usize) -> Self {
+        Self {
+            span,
+            error: CompileErrorEnum::ArgumentCount { expected, found },
+            note: None,
+        }
+    }
+
+    pub fn new_assign_to_const(span: Span, name: String) -> Self {
+        Self {
+            span,
+            error: CompileErrorEnum::AssignToConst(name),
+            note: None,
+        }
+    }
+
+    pub fn new_invalid_assign_target(span: Span, ty: String) -> Self {
+        Self {
+            span,
+            error: CompileErrorEnum::InvalidAssignTarget(ty),
+            note: None,
+        }
+    }
+
+    pub fn new_no_field(span: Span, ty: String, field: String) -> Self {
+        Self {
+            span,
+            error: CompileErrorEnum::NoField { ty, field },
+            note: None,
+        }
+    }
+
+    pub fn new_duplicate_field(span: Span, field: String) -> Self {
+        Self {
+            span,
+            error: CompileErrorEnum::DuplicateField(field),
+            note: None,
+        }
+    }
+
+    pub fn new_missing_fields(span: Span, ty: String, fields: String) -> Self {
+        Self {
+            span,
+            error: CompileErrorEnum::MissingFields { ty, fields },
+            note: None,
+        }
+    }
+
+    pub fn new_no_variant(span: Span, ty: String, variant: String) -> Self {
+        Self {
+            span,
+            error: CompileErrorEnum::NoVariant { ty, variant },
+            note: None,
+        }
+    }
+
+    pub fn new_duplicate_variant(span: Span, variant: String) -> Self {
+        Self {
+            span,
+            error: CompileErrorEnum::DuplicateVariant(variant),
+            note: None,
+        }
+    }
+
+    pub fn new_discriminant_overflow(span: Span, value: i128, ty: String) -> Self {
+        Self {
+            span,
+            error: CompileErrorEnum::DiscriminantOverflow { value, ty },
+            note: None,
+        }
+    }
+
+    pub fn new_duplicate_discriminant(span: Span, value: i128, variant: String) -> Self {
+        Self {
+            span,
+            error: CompileErrorEnum::DuplicateDiscriminant { value, variant },
+            note: None,
+        }
+    }
+
+    pub fn new_missing_payload(span: Span, ty: String, variant: String) -> Self {
+        Self {
+            span,
+            error: CompileErrorEnum::MissingPayload { ty, variant },
+            note: None,
+        }
+    }
+
+    pub fn new_no_payload(span: Span, ty: String, variant: String) -> Self {
+        Self {
+            span,
+            error: CompileErrorEnum::NoPayload { ty, variant },
+            note: None,
+        }
+    }
+
+    pub fn new_invalid_union_init(span: Span, ty: String) -> Self {
+        Self {
+            span,
+            error: CompileErrorEnum::InvalidUnionInit(ty),
+            note: None,
+        }
+    }
+
+    pub fn new_non_exhaustive(span: Span, missing: String) -> Self {
+        Self {
+            span,
+            error: CompileErrorEnum::NonExhaustive(missing),
+            note: None,
+        }
+    }
+
+    pub fn new_duplicate_arm(span: Span, variant: String) -> Self {
+        Self {
+            span,
+            error: CompileErrorEnum::DuplicateArm(variant),
+            note: None,
+        }
+    }
+
+    pub fn new_duplicate_case(span: Span, case: String) -> Self {
+        Self {
+            span,
+            error: CompileErrorEnum::DuplicateCase(case),
+            note: None,
+        }
+    }
+
+    pub fn new_overlapping_case(span: Span, case: String) -> Self {
+        Self {
+            span,
+            error: CompileErrorEnum::OverlappingCase(case),
+            note: None,
+        }
+    }
+
+    pub fn new_empty_range(span: Span, range: String) -> Self {
+        Self {
+            span,
+            error: CompileErrorEnum::EmptyRange(range),
+            note: None,
+        }
+    }
+
+    pub fn new_reversed_slice(span: Span, start: u64, end: u64) -> Self {
+        Self {
+            span,
+            error: CompileErrorEnum::ReversedSlice { start, end },
+            note: None,
+        }
+    }
+
+    pub fn new_invalid_code_point(span: Span, code_point: u32) -> Self {
+        Self {
+            span,
+            error: CompileErrorEnum::InvalidCodePoint(code_point),
+            note: None,
+        }
+    }
+
+    pub fn new_literal_overflow(span: Span, value: String, ty: String) -> Self {
+        Self {
+            span,
+            error: CompileErrorEnum::LiteralOverflow { value, ty },
+            note: None,
+        }
+    }
+
+    pub fn new_no_export(span: Span, module: String, name: String) -> Self {
+        Self {
+            span,
+            error: CompileErrorEnum::NoExport { module, name },
+            note: None,
+        }
+    }
+
+    pub fn new_module_not_found(span: Span, path: String) -> Self {
+        Self {
+            span,
+            error: CompileErrorEnum::ModuleNotFound(path),
+            note: None,
+        }
+    }
+
+    pub fn new_import_cycle(span: Span, cycle: String) -> Self {
+        Self {
+            span,
+            error: CompileErrorEnum::ImportCycle(cycle),
+            note: None,
+        }
+    }
+
+    pub fn new_misplaced_import(span: Span) -> Self {
+        Self {
+            span,
+            error: CompileErrorEnum::MisplacedImport,
+            note: None,
+        }
+    }
+
+    pub fn new_local_pub(span: Span) -> Self {
+        Self {
+            span,
+            error: CompileErrorEnum::LocalPub,
+            note: None,
+        }
+    }
+
+    pub fn new_index_out_of_bounds(span: Span, index: u64, len: u64) -> Self {
+        Self {
+            span,
+            error: CompileErrorEnum::IndexOutOfBounds { index, len },
+            note: None,
+        }
+    }
+
+    pub fn new_array_copy(span: Span) -> Self {
+        Self {
+            span,
+            error: CompileErrorEnum::ArrayCopy,
+            note: None,
+        }
+    }
+
+    pub fn new_cannot_infer(span: Span, ty: String) -> Self {
+        Self {
+            span,
+            error: CompileErrorEnum::CannotInfer(ty),
+            note: None,
         }
     }
 }
 
 impl Display for CompileError {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        self.span.show(f, format!("{}", self.error))
+        self.span.show(f, format!("{}", self.error))?;
+
+        if let Some((span, note)) = &self.note {
+            writeln!(f)?;
+            span.show_note(f, note.clone())?;
+        }
+
+        Ok(())
     }
 }
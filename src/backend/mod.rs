@@ -1,10 +1,12 @@
 use crate::ast::*;
 use crate::cgen::*;
-use alloc::{sync::Arc,vec::Vec};
+use alloc::{string::String, sync::Arc, vec::Vec};
 use spin::RwLock;
 
 mod codegen;
 mod error;
+#[cfg(test)]
+mod tests;
 
 pub use error::*;
 
@@ -15,6 +17,49 @@ pub struct CodegenContext {
     pub global: SymbolTable,
     pub func_id: usize,
     pub errors: Vec<CompileError>,
+    pub blocks: Vec<CBlock>,
+    pub loops: Vec<Loop>,
+    pub loop_id: usize,
+    pub temp_id: usize,
+    // Prepended to the C names of the current module's globals.
+    pub prefix: Option<String>,
+    pub imports: Vec<(String, usize)>,
+    pub exports: SymbolTable,
+    pub modules: Vec<CType>,
+}
+
+// A parsed source file. Only the root module keeps its C names as written,
+// so that different modules can use the same names.
+pub struct Module {
+    pub name: String,
+    pub prefix: Option<String>,
+    // Import paths as written, with the index of the module they load.
+    pub imports: Vec<(String, usize)>,
+    pub ast: CompUnit,
+}
+
+pub struct Loop {
+    pub label: Option<String>,
+    pub id: usize,
+    pub break_used: bool,
+    pub continue_used: bool,
+    // Number of switches entered since the loop, where a plain `break` would
+    // only leave the switch.
+    pub switch_depth: usize,
+}
+
+impl Loop {
+    pub fn break_label(&self) -> String {
+        alloc::format!("_cara_loop{}_break", self.id)
+    }
+
+    pub fn continue_label(&self) -> String {
+        alloc::format!("_cara_loop{}_continue", self.id)
+    }
+
+    pub fn end_name(&self) -> String {
+        alloc::format!("_cara_loop{}_end", self.id)
+    }
 }
 
 pub trait Codegen {
@@ -32,6 +77,14 @@ impl CodegenContext {
             current_function: None,
             func_id: 0,
             errors: Vec::new(),
+            blocks: Vec::new(),
+            loops: Vec::new(),
+            loop_id: 0,
+            temp_id: 0,
+            prefix: None,
+            imports: Vec::new(),
+            exports: SymbolTable::new(),
+            modules: Vec::new(),
         }
     }
 
@@ -43,6 +96,121 @@ impl CodegenContext {
         self.local.pop();
     }
 
+    pub fn get_symbol(&self, name: &str) -> Option<&Symbol> {
+        self.local.get(name).or_else(|| self.global.get(name))
+    }
+
+    // Looks up a name that may be qualified by modules, as in `io.File`.
+    pub fn get_path(&self, path: &[String], span: Span) -> anyhow::Result<Value> {
+        let value = match self.get_symbol(&path[0]) {
+            Some(Symbol::Const(_, value)) | Some(Symbol::Var(_, value)) => value.clone(),
+            _ => return Err(CompileError::new_symbol_not_found(span, path[0].clone()).into()),
+        };
+
+        path[1..].iter().try_fold(value, |value, name| {
+            value.as_type(span.clone())?.variant(name, span.clone())
+        })
+    }
+
+    // The symbol a path starts with once module names are looked through, so
+    // `io.counter.x` finds `counter` in `io`.
+    pub fn get_path_symbol(&self, path: &[String], span: Span) -> Option<(String, Symbol)> {
+        let mut name = path[0].clone();
+        let mut symbol = self.get_symbol(&name)?.clone();
+        for id in path[1..].iter() {
+            let module = match &symbol {
+                Symbol::Const(_, value) => value.as_type(span.clone()).ok(),
+                _ => None,
+            };
+            let Some(module) = module.filter(|module| module.is_module()) else {
+                break;
+            };
+            symbol = module.get_symbol(id)?.clone();
+            name = id.clone();
+        }
+
+        Some((name, symbol))
+    }
+
+    pub fn get_import(&self, path: &str, span: Span) -> anyhow::Result<Value> {
+        match self.imports.iter().find(|(import, _)| import == path) {
+            Some((_, index)) => Ok(Value::new_type(self.modules[*index].clone())),
+            None => Err(CompileError::new_misplaced_import(span).into()),
+        }
+    }
+
+    pub fn export(&mut self, name: &str) {
+        if let Some(symbol) = self.global.get(name).cloned() {
+            self.exports.push(symbol);
+        }
+    }
+
+    pub fn c_name(&self, name: &str) -> String {
+        match &self.prefix {
+            Some(prefix) => alloc::format!("{}{}", prefix, name),
+            None => name.into(),
+        }
+    }
+
+    pub fn push_block(&mut self) {
+        self.blocks.push(CBlock::new());
+    }
+
+    pub fn pop_block(&mut self) -> CBlock {
+        self.blocks.pop().unwrap()
+    }
+
+    pub fn insert_block_item(&mut self, item: CBlockItem) {
+        self.blocks.last_mut().unwrap().push(item);
+    }
+
+    pub fn push_loop(&mut self, label: Option<String>) {
+        let id = self.loop_id;
+        self.loop_id += 1;
+        self.loops.push(Loop {
+            label,
+            id,
+            break_used: false,
+            continue_used: false,
+            switch_depth: 0,
+        });
+    }
+
+    pub fn pop_loop(&mut self) -> Loop {
+        self.loops.pop().unwrap()
+    }
+
+    pub fn push_switch(&mut self) {
+        if let Some(r#loop) = self.loops.last_mut() {
+            r#loop.switch_depth += 1;
+        }
+    }
+
+    pub fn pop_switch(&mut self) {
+        if let Some(r#loop) = self.loops.last_mut() {
+            r#loop.switch_depth -= 1;
+        }
+    }
+
+    pub fn find_loop(&mut self, label: &str) -> Option<&mut Loop> {
+        self.loops
+            .iter_mut()
+            .rev()
+            .find(|r#loop| r#loop.label.as_deref() == Some(label))
+    }
+
+    pub fn new_temp_name(&mut self, kind: &str) -> String {
+        let id = self.temp_id;
+        self.temp_id += 1;
+        alloc::format!("_cara_{}{}", kind, id)
+    }
+
+    pub fn new_slice_type(&mut self, element_type: CType) -> CType {
+        let slice_type = CSliceType::new(element_type);
+        self.c_program.insert_slice_type(slice_type.clone());
+        CType::new_slice(slice_type)
+    }
+
     pub fn new_func_id(&mut self) -> usize {
         let id = self.func_id;
         self.func_id += 1;
@@ -0,0 +1,718 @@
+use super::*;
+use crate::parser::CParser;
+use alloc::string::ToString;
+
+fn codegen(code: &str) -> Result<String, Vec<CompileError>> {
+    let module = Module {
+        name: "test".into(),
+        prefix: None,
+        imports: Vec::new(),
+        ast: CParser::new(code.into(), "test.cara".into()).parse(),
+    };
+
+    crate::compile(&[module], true)
+}
+
+fn compile(code: &str) -> Result<String, Vec<CompileErrorEnum>> {
+    codegen(code).map_err(|errors| errors.into_iter().map(|error| error.error).collect())
+}
+
+// The first error reported for a program that should not compile.
+fn error(code: &str) -> CompileErrorEnum {
+    match compile(code) {
+        Ok(c_program) => panic!("expected an error, got:\n{}", c_program),
+        Err(errors) => errors.into_iter().next().unwrap(),
+    }
+}
+
+// Wraps statements in a `main` function.
+fn in_main(body: &str) -> String {
+    alloc::format!("const main = fn() i32 {{ {} return 0; }};", body)
+}
+
+#[test]
+fn else_if_chain() {
+    let c_program = compile(&in_main(
+        "var x: i32 = 2; if x == 1 { x = 10; } else if x == 2 { x = 20; } else { x = 30; }",
+    ))
+    .unwrap();
+    assert!(c_program.contains(
+        "if ((x == 1)) {\nx = 10;\n} else {\nif ((x == 2)) {\nx = 20;\n} else {\nx = 30;\n}\n}"
+    ));
+}
+
+#[test]
+fn non_bool_condition() {
+    assert_eq!(
+        error(&in_main("var x: i32 = 1; if x { }")),
+        CompileErrorEnum::MismatchedType {
+            expected: "bool".to_string(),
+            found: "int".to_string(),
+        }
+    );
+    assert_eq!(
+        error(&in_main("if true { } else if 1 { }")),
+        CompileErrorEnum::MismatchedType {
+            expected: "bool".to_string(),
+            found: "int".to_string(),
+        }
+    );
+}
+
+#[test]
+fn break_outside_of_loop() {
+    assert_eq!(
+        error(&in_main("break;")),
+        CompileErrorEnum::OutsideOfLoop("break".to_string())
+    );
+    assert_eq!(
+        error(&in_main("if true { continue; }")),
+        CompileErrorEnum::OutsideOfLoop("continue".to_string())
+    );
+}
+
+#[test]
+fn break_inside_of_loop() {
+    assert!(compile(&in_main("while true { if false { continue; } break; }")).is_ok());
+}
+
+#[test]
+fn undeclared_label() {
+    assert_eq!(
+        error(&in_main("outer: while true { break :inner; }")),
+        CompileErrorEnum::UndeclaredLabel("inner".to_string())
+    );
+    assert_eq!(
+        error(&in_main("while true { continue :outer; }")),
+        CompileErrorEnum::UndeclaredLabel("outer".to_string())
+    );
+}
+
+#[test]
+fn labeled_break_leaves_outer_loop() {
+    let c_program = compile(&in_main(
+        "outer: while true { while true { break :outer; } }",
+    ))
+    .unwrap();
+    assert!(c_program.contains("goto _cara_loop0_break;"));
+}
+
+#[test]
+fn range_bounds_fit_loop_variable() {
+    assert_eq!(
+        error(&in_main("for i in 0u8..300 { }")),
+        CompileErrorEnum::LiteralOverflow {
+            value: "300".to_string(),
+            ty: "u8".to_string(),
+        }
+    );
+    assert_eq!(
+        error(&in_main("for i in 0..true { }")),
+        CompileErrorEnum::MismatchedType {
+            expected: "int".to_string(),
+            found: "bool".to_string(),
+        }
+    );
+    assert_eq!(
+        error(&in_main("var n: u8 = 3; for i in -1..n { }")),
+        CompileErrorEnum::LiteralOverflow {
+            value: "-1".to_string(),
+            ty: "u8".to_string(),
+        }
+    );
+    assert!(compile(&in_main("var n: u8 = 3; for i in 0..n { }")).is_ok());
+}
+
+#[test]
+fn assign_targets() {
+    let c_program = compile(&in_main(
+        "var x: i32 = 0; var p: *i32 = &x; *p = 1; var a: [2]i32 = [1, 2]; a[1] += x;",
+    ))
+    .unwrap();
+    assert!(c_program.contains("(*p) = 1;"));
+
+    assert_eq!(
+        error(&in_main("var p: i32 = 0; *p = 1;")),
+        CompileErrorEnum::MismatchedType {
+            expected: "pointer".to_string(),
+            found: "int".to_string(),
+        }
+    );
+    assert_eq!(
+        error(&in_main("var a: [2]i32 = [1, 2]; a = a;")),
+        CompileErrorEnum::InvalidAssignTarget("[2]i32".to_string())
+    );
+}
+
+#[test]
+fn assign_to_const() {
+    assert_eq!(
+        error("const n = 1; const main = fn() i32 { n = 2; return 0; };"),
+        CompileErrorEnum::AssignToConst("n".to_string())
+    );
+    assert_eq!(
+        error("const f = fn(const x: i32) i32 { x += 1; return x; };"),
+        CompileErrorEnum::AssignToConst("x".to_string())
+    );
+}
+
+#[test]
+fn chained_comparison() {
+    assert_eq!(
+        error(&in_main(
+            "var a = 1; var b = 2; var c = 3; var x = a < b < c;"
+        )),
+        CompileErrorEnum::InvalidOperand {
+            op: "<".to_string(),
+            ty: "_Bool".to_string(),
+        }
+    );
+    assert_eq!(
+        error(&in_main("var x = 1 == 1 == 1;")),
+        CompileErrorEnum::MismatchedType {
+            expected: "bool".to_string(),
+            found: "int".to_string(),
+        }
+    );
+    assert!(
+        compile(&in_main(
+            "var a = 6; var x: bool = a & 2 == 2; var y = 1 < 2 == true;"
+        ))
+        .is_ok()
+    );
+}
+
+#[test]
+fn logical_operands() {
+    assert_eq!(
+        error(&in_main(
+            "var x: i32 = 1; var p: *i32 = &x; var b = 1 && p;"
+        )),
+        CompileErrorEnum::MismatchedType {
+            expected: "bool".to_string(),
+            found: "int".to_string(),
+        }
+    );
+    assert_eq!(
+        error(&in_main("var b = true || 0;")),
+        CompileErrorEnum::MismatchedType {
+            expected: "bool".to_string(),
+            found: "int".to_string(),
+        }
+    );
+    let c_program = compile(&in_main(
+        "var a = 1; var b = 2; var c = true; var x = a < b && c; var y = !c || x;",
+    ))
+    .unwrap();
+    assert!(c_program.contains("((a < b) && c)"));
+    assert!(c_program.contains("((!c) || x)"));
+}
+
+#[test]
+fn shift_amount() {
+    assert_eq!(
+        error(&in_main("var x: u32 = 1; x = x << 32;")),
+        CompileErrorEnum::ShiftOverflow {
+            amount: 32,
+            ty: "u32".to_string(),
+        }
+    );
+    assert_eq!(
+        error(&in_main("var x: u8 = 1; x >>= 8;")),
+        CompileErrorEnum::ShiftOverflow {
+            amount: 8,
+            ty: "u8".to_string(),
+        }
+    );
+    assert_eq!(
+        error(&in_main("var x: u32 = 1; x = x << -1;")),
+        CompileErrorEnum::NegativeShift(-1)
+    );
+    assert!(compile(&in_main("var x: u32 = 1; x = x << 31; x >>= 0;")).is_ok());
+}
+
+#[test]
+fn unary_operands() {
+    let invalid = |op: &str, ty: &str| CompileErrorEnum::InvalidOperand {
+        op: op.to_string(),
+        ty: ty.to_string(),
+    };
+    assert_eq!(error(&in_main("var b = -true;")), invalid("-", "_Bool"));
+    assert_eq!(error(&in_main("var b = ~true;")), invalid("~", "_Bool"));
+    assert_eq!(error(&in_main("var f = ~1.5;")), invalid("~", "f64"));
+    assert_eq!(
+        error(&in_main("var b = !1;")),
+        CompileErrorEnum::MismatchedType {
+            expected: "bool".to_string(),
+            found: "int".to_string(),
+        }
+    );
+    assert!(
+        compile(&in_main(
+            "var a = -1; var b = ~a; var f = -1.5; var c = !true;"
+        ))
+        .is_ok()
+    );
+}
+
+#[test]
+fn call_arguments() {
+    let take = "extern fn take(b: bool) void;";
+    assert_eq!(
+        error(&alloc::format!("{} {}", take, in_main("take(1);"))),
+        CompileErrorEnum::MismatchedType {
+            expected: "bool".to_string(),
+            found: "int".to_string(),
+        }
+    );
+    assert_eq!(
+        error(&alloc::format!(
+            "{} {}",
+            take,
+            in_main("take(true, false);")
+        )),
+        CompileErrorEnum::ArgumentCount {
+            expected: 1,
+            found: 2,
+        }
+    );
+    assert_eq!(
+        error(&alloc::format!("{} {}", take, in_main("take();"))),
+        CompileErrorEnum::ArgumentCount {
+            expected: 1,
+            found: 0,
+        }
+    );
+    assert!(compile(&alloc::format!("{} {}", take, in_main("take(1 < 2);"))).is_ok());
+}
+
+#[test]
+fn call_non_function() {
+    assert_eq!(
+        error(&in_main("var x: i32 = 1; x();")),
+        CompileErrorEnum::InvalidTypeCast {
+            from: "int".to_string(),
+            to: "function".to_string(),
+        }
+    );
+    assert!(
+        compile(
+            "const one = fn() i32 { return 1; };
+            const main = fn() i32 { var x: i32 = one(); return x; };"
+        )
+        .is_ok()
+    );
+}
+
+#[test]
+fn return_values() {
+    assert_eq!(
+        error("const main = fn() i32 { return; };"),
+        CompileErrorEnum::MismatchedType {
+            expected: "int".to_string(),
+            found: "void".to_string(),
+        }
+    );
+    assert_eq!(
+        error("const f = fn() void { return 1; }; const main = fn() i32 { return 0; };"),
+        CompileErrorEnum::MismatchedType {
+            expected: "void".to_string(),
+            found: "int".to_string(),
+        }
+    );
+    assert!(
+        compile("const f = fn() void { return; }; const main = fn() i32 { return 0; };").is_ok()
+    );
+}
+
+#[test]
+fn integer_literal_range() {
+    let overflow = |value: &str, ty: &str| CompileErrorEnum::LiteralOverflow {
+        value: value.to_string(),
+        ty: ty.to_string(),
+    };
+    assert_eq!(error(&in_main("var a = 128i8;")), overflow("128", "i8"));
+    assert_eq!(error(&in_main("var a = -129i8;")), overflow("129", "i8"));
+    assert_eq!(error(&in_main("var a: i8 = -129;")), overflow("-129", "i8"));
+    assert_eq!(error(&in_main("var a: u8 = 256;")), overflow("256", "u8"));
+    assert_eq!(error(&in_main("var a = -1u8;")), overflow("-1", "u8"));
+    assert_eq!(
+        error(&in_main("var a = 18446744073709551616;")),
+        overflow("18446744073709551616", "u64")
+    );
+    assert!(
+        compile(&in_main(
+            "var a = -128i8; var b: i8 = -128; var c: i8 = 127; var d: u64 = 18446744073709551615;
+            var e: i64 = -9223372036854775808;"
+        ))
+        .is_ok()
+    );
+}
+
+#[test]
+fn signed_minimum_literals() {
+    let c_program = compile(&in_main(
+        "var a = -128i8; var b = -2147483648i32; var c = -9223372036854775808i64;
+        var x: i64 = 5; var d = x < -9223372036854775808;",
+    ))
+    .unwrap();
+    assert!(c_program.contains("i8 a = (-128);"));
+    assert!(c_program.contains("i32 b = (-2147483648);"));
+    // A negated `9223372036854775808` would be unsigned in C.
+    assert!(c_program.contains("i64 c = (-9223372036854775807LL - 1);"));
+    assert!(c_program.contains("_Bool d = (x < (-9223372036854775807LL - 1));"));
+}
+
+#[test]
+fn float_literal_range() {
+    assert_eq!(
+        error(&in_main("var f: f64 = 1e999;")),
+        CompileErrorEnum::LiteralOverflow {
+            value: "1e999".to_string(),
+            ty: "f64".to_string(),
+        }
+    );
+    assert_eq!(
+        error(&in_main("var f: f32 = -1e39;")),
+        CompileErrorEnum::LiteralOverflow {
+            value: "-1e39".to_string(),
+            ty: "f32".to_string(),
+        }
+    );
+    assert_eq!(
+        error(&in_main("var f: f32 = 1.0; f = f * 1e40;")),
+        CompileErrorEnum::LiteralOverflow {
+            value: "1e40".to_string(),
+            ty: "f32".to_string(),
+        }
+    );
+    assert!(compile(&in_main("var f: f32 = 3.4e38; var g: f64 = 1e300;")).is_ok());
+}
+
+#[test]
+fn invalid_casts() {
+    let cast = |from: &str, to: &str| CompileErrorEnum::InvalidTypeCast {
+        from: from.to_string(),
+        to: to.to_string(),
+    };
+    assert_eq!(
+        error(
+            "const P = struct { x: i32 };
+            const main = fn() i32 { var p = P { .x = 1 }; var y = p as i32; return 0; };"
+        ),
+        cast("P", "int")
+    );
+    assert_eq!(
+        error(&in_main("var f = 1.5; var q = f as *u8;")),
+        cast("float", "*u8")
+    );
+    assert_eq!(
+        error(&in_main("var x: i32 = 1; var p = &x; var f = p as f64;")),
+        cast("*i32", "float")
+    );
+    assert_eq!(
+        error(&in_main("var a: [2]i32 = [1, 2]; var n = a as i64;")),
+        cast("[2]i32", "int")
+    );
+    assert_eq!(
+        error(&in_main(
+            "var a: [2]i32 = [1, 2]; var s: []i32 = a[0..2]; var n = s as u64;"
+        )),
+        cast("[]i32", "int")
+    );
+    assert_eq!(
+        error(&in_main("var b = true as f32;")),
+        cast("bool", "float")
+    );
+    assert!(
+        compile(&in_main(
+            "var a: i32 = 1; var b = a as u8; var f = a as f32; var g = f as f64; var n = g as i64;
+            var p = a as *u8; var q = p as *const i32; var m = q as u64; var t = 3 as bool; var u = t as u64;"
+        ))
+        .is_ok()
+    );
+}
+
+#[test]
+fn local_types_are_distinct() {
+    let c_program = compile(
+        "const P = struct { x: i32 };
+        const f = fn() i32 { const P = struct { y: i32 }; var p: P = P { .y = 1 }; return p.y; };
+        const g = fn() i32 { const P = enum(u8) { A }; var p: P = P.A; return p as i32; };
+        const main = fn() i32 { var p: P = P { .x = 1 }; return p.x + f() + g(); };",
+    )
+    .unwrap();
+    assert!(c_program.contains("struct P {"));
+    assert!(c_program.contains("struct _cara_P_0 {"));
+    assert!(c_program.contains("typedef u8 _cara_P_1;"));
+}
+
+#[test]
+fn local_consts_are_distinct() {
+    let c_program = compile(
+        "const f = fn() i32 { const N = 5; return N; };
+        const main = fn() i32 { var x: i32 = 1; const N = 6; const p = &x; *p = N; return f(); };",
+    )
+    .unwrap();
+    // Each constant is declared in its function, after the variables it uses.
+    let f = c_program.find("i64 const _cara_N_0 = 5;").unwrap();
+    let main = c_program.find("i64 const _cara_N_1 = 6;").unwrap();
+    let x = c_program.find("i32 x = 1;").unwrap();
+    let p = c_program.find("i32 *const _cara_p_2 = &x;").unwrap();
+    assert!(f < main && x < main && main < p);
+    assert!(c_program.contains("(*_cara_p_2) = _cara_N_1;"));
+}
+
+#[test]
+fn enum_discriminants() {
+    let c_program = compile(
+        "const E = enum(i8) { A = -1, B, C = -128 };
+        const main = fn() i32 { var e: E = E.C; return e as i32 + E.B as i32; };",
+    )
+    .unwrap();
+    assert!(c_program.contains("((E)-128)"));
+    assert!(c_program.contains("((E)0)"));
+
+    assert_eq!(
+        error("const E = enum(i8) { A = -129 };"),
+        CompileErrorEnum::DiscriminantOverflow {
+            value: -129,
+            ty: "i8".to_string(),
+        }
+    );
+    assert_eq!(
+        error("const E = enum(u8) { A = -1 };"),
+        CompileErrorEnum::DiscriminantOverflow {
+            value: -1,
+            ty: "u8".to_string(),
+        }
+    );
+    assert_eq!(
+        error("const E = enum(i16) { A = -2, B = -1, C = -1 };"),
+        CompileErrorEnum::DuplicateDiscriminant {
+            value: -1,
+            variant: "B".to_string(),
+        }
+    );
+}
+
+#[test]
+fn enum_casts() {
+    let types = "const Color = enum(u8) { Red, Green = 5 };
+        const Shape = union(enum) { Circle: i32, Empty: void };
+        const P = struct { x: i32 };";
+    let cast = |from: &str, to: &str| CompileErrorEnum::InvalidTypeCast {
+        from: from.to_string(),
+        to: to.to_string(),
+    };
+    assert!(
+        compile(&alloc::format!(
+            "{} {}",
+            types,
+            in_main("var c = Color.Green; var n = c as u8; var m = c as i64; var d = 5 as Color;")
+        ))
+        .is_ok()
+    );
+    assert_eq!(
+        error(&alloc::format!(
+            "{} {}",
+            types,
+            in_main("var s = Shape { .Circle = 1 }; var n = s as i32;")
+        )),
+        cast("Shape", "int")
+    );
+    assert_eq!(
+        error(&alloc::format!(
+            "{} {}",
+            types,
+            in_main("var s = 1 as Shape;")
+        )),
+        cast("int", "Shape")
+    );
+    assert_eq!(
+        error(&alloc::format!("{} {}", types, in_main("var p = 1 as P;"))),
+        cast("int", "P")
+    );
+    assert_eq!(
+        error(&alloc::format!(
+            "{} {}",
+            types,
+            in_main("var c = Color.Red; var s = c as Shape;")
+        )),
+        cast("Color", "Shape")
+    );
+}
+
+const SHAPE: &str = "const Shape = union(enum) { Circle: i32, Square: i32, Empty: void };";
+
+#[test]
+fn non_exhaustive_match() {
+    assert_eq!(
+        error(&alloc::format!(
+            "{} const f = fn(s: Shape) i32 {{ match s {{ Circle(r) => {{ return r; }} }} return 0; }};",
+            SHAPE
+        )),
+        CompileErrorEnum::NonExhaustive("`Square`, `Empty`".to_string())
+    );
+    assert_eq!(
+        error(&alloc::format!(
+            "{} const f = fn(s: Shape) i32 {{ match s {{ Empty => {{ }} Empty => {{ }} }} return 0; }};",
+            SHAPE
+        )),
+        CompileErrorEnum::DuplicateArm("Empty".to_string())
+    );
+    assert!(
+        compile(&alloc::format!(
+            "{} const f = fn(s: Shape) i32 {{ match s {{ Circle(r) => {{ return r; }} Square(w) => {{ return w; }} Empty => {{ }} }} return 0; }};",
+            SHAPE
+        ))
+        .is_ok()
+    );
+}
+
+#[test]
+fn duplicate_switch_cases() {
+    assert_eq!(
+        error(&in_main(
+            "var x: i32 = 0; switch x { 1, 2 => { } 2 => { } else => { } }"
+        )),
+        CompileErrorEnum::DuplicateCase("2".to_string())
+    );
+    assert_eq!(
+        error(&in_main(
+            "var x: i32 = 0; switch x { 1..5 => { } 3 => { } else => { } }"
+        )),
+        CompileErrorEnum::OverlappingCase("3".to_string())
+    );
+    assert_eq!(
+        error(&in_main(
+            "var x: i32 = 0; switch x { 5..1 => { } else => { } }"
+        )),
+        CompileErrorEnum::EmptyRange("5..1".to_string())
+    );
+
+    // Both cases are shown.
+    let errors = codegen(&in_main(
+        "var x: i32 = 0;\nswitch x {\n1 => { }\n1 => { }\nelse => { }\n}",
+    ))
+    .unwrap_err();
+    let (note_span, _) = errors[0].note.as_ref().unwrap();
+    assert!(alloc::format!("{:?}", errors[0].span).contains("start: (4, 1)"));
+    assert!(alloc::format!("{:?}", note_span).contains("start: (3, 1)"));
+}
+
+#[test]
+fn repeated_array_items() {
+    let c_program = compile(
+        "var zeros: [3]i32 = [0; 3];
+        const one = fn() i32 { return 1; };
+        const main = fn() i32 { var ones: [2]i32 = [one(); 2]; return ones[0] + zeros[1]; };",
+    )
+    .unwrap();
+    assert!(c_program.contains("{0, 0, 0}"));
+    assert!(c_program.contains("i32 _cara_repeat0 = (one)();"));
+    assert!(c_program.contains("{_cara_repeat0, _cara_repeat0}"));
+}
+
+#[test]
+fn array_loop_counters() {
+    let c_program = compile(&in_main(
+        "var a: [2]i32 = [1, 2]; for x in a { for y in a { } }",
+    ))
+    .unwrap();
+    assert!(c_program.contains("_cara_index0"));
+    assert!(c_program.contains("_cara_index1"));
+}
+
+#[test]
+fn slice_loop_counters() {
+    let c_program = compile(&in_main(
+        "var a: [2]i32 = [1, 2]; var s: []i32 = a[0..2]; for x in s { for y in s { } }",
+    ))
+    .unwrap();
+    assert!(c_program.contains("i32 *ptr;"));
+    assert!(c_program.contains("_cara_index1"));
+    assert!(c_program.contains("_cara_index3"));
+}
+
+#[test]
+fn assign_target_generated_once() {
+    let c_program = compile(
+        "const one = fn() i32 { return 1; };
+        const main = fn() i32 { [one(); 2][0] = 5; var x: [2]i32 = [1, 2]; var p: *i32 = &x[1]; return 0; };",
+    )
+    .unwrap();
+    assert_eq!(c_program.matches("= (one)();").count(), 1);
+
+    assert_eq!(
+        error("const a = [1, 2]; const main = fn() i32 { a[0] = 3; return 0; };"),
+        CompileErrorEnum::AssignToConst("a".to_string())
+    );
+    // Constant pointers and slices can still write to what they point to.
+    assert!(
+        compile(
+            "var x: [2]i32 = [1, 2]; const p = &x[0]; const s = x[0..2];
+            const main = fn() i32 { *p = 1; s[1] = 2; return 0; };"
+        )
+        .is_ok()
+    );
+}
+
+#[test]
+fn pointer_arithmetic() {
+    let c_program = compile(&in_main(
+        "var a: [4]u32 = [1, 2, 3, 4]; var p: *u32 = &a[0]; var q: *u32 = &a[3];
+        var d = q - p; var r = p + 2; var c: *const u32 = p; var w = c < p;",
+    ))
+    .unwrap();
+    assert!(c_program.contains("i64 d = (q - p);"));
+    assert!(c_program.contains("u32 *r = (p + 2);"));
+    assert!(c_program.contains("_Bool w = (c < p);"));
+
+    assert_eq!(
+        error(&in_main(
+            "var x: u32 = 1; var y: i32 = 2; var p: *u32 = &x; var q: *i32 = &y; var b = p < q;"
+        )),
+        CompileErrorEnum::MismatchedType {
+            expected: "*u32".to_string(),
+            found: "*i32".to_string(),
+        }
+    );
+    assert_eq!(
+        error(&in_main("var x: u32 = 1; var p = &x; var r = p + p;")),
+        CompileErrorEnum::InvalidOperand {
+            op: "+".to_string(),
+            ty: "u32 *".to_string(),
+        }
+    );
+}
+
+#[test]
+fn invalid_string_code_point() {
+    assert_eq!(
+        error(&in_main("var s: *const u8 = \"a\\uD800b\";")),
+        CompileErrorEnum::InvalidCodePoint(0xD800)
+    );
+    assert!(compile(&in_main("var s: *const u8 = \"a\\u00e9b\";")).is_ok());
+}
+
+#[test]
+fn char_literals() {
+    let c_program = compile(&in_main(
+        r"var c = '\x7f'; var e = '\u{1F600}'; var n = '\n'; var a = 'a';",
+    ))
+    .unwrap();
+    assert!(c_program.contains("u8 c = 127;"));
+    assert!(c_program.contains("u32 e = 128512;"));
+    assert!(c_program.contains("u8 n = 10;"));
+    assert!(c_program.contains("u8 a = 97;"));
+
+    assert_eq!(
+        error(&in_main(r"var c = '\u{D800}';")),
+        CompileErrorEnum::InvalidCodePoint(0xD800)
+    );
+    assert_eq!(
+        error(&in_main(r"var c = '\u{110000}';")),
+        CompileErrorEnum::InvalidCodePoint(0x110000)
+    );
+}
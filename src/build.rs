@@ -0,0 +1,65 @@
+use crate::{
+    c_compile::*,
+    generate,
+    loader::Loader,
+    manifest::{self, MANIFEST_NAME},
+};
+use std::{format, path::Path, vec, vec::Vec};
+
+// Builds the package in the current directory into `target/`, compiling its
+// dependencies' Cara and C sources into the same executable.
+pub fn build(bounds_checks: bool) -> anyhow::Result<()> {
+    let packages = manifest::load_packages(Path::new("."))?;
+
+    let mut loader = Loader::new();
+    for package in packages.iter() {
+        loader.add_package(
+            package
+                .dependencies
+                .iter()
+                .map(|(name, index)| (name.clone(), packages[*index].entry.clone(), *index))
+                .collect(),
+        );
+    }
+    let root = &packages[0];
+    let modules = loader.load(&root.entry, Some(0))?;
+    let Some(c_program) = generate(&modules, bounds_checks) else {
+        return Err(anyhow::anyhow!("could not compile `{}`", root.name));
+    };
+
+    let target = root.root.join("target");
+    std::fs::create_dir_all(&target)?;
+    let c_file = target.join(format!("{}.c", root.name));
+    std::fs::write(&c_file, format!("{}\n", c_program))?;
+
+    let mut compiler_settings = CompilerSettings::new();
+    compiler_settings.set_compiler_type(CCompilerType::GCC);
+    let mut linker_settings = LinkerSettings::new();
+    linker_settings.set_linker_type(LinkerType::Mold);
+
+    let object = target.join(format!("{}.o", root.name));
+    compiler_settings.compile(c_file, object.clone())?;
+    let mut objects: Vec<_> = vec![object, runtime_library()?];
+
+    for package in packages.iter() {
+        for source in package.c_sources.iter() {
+            // Sources in different directories or packages can share a name,
+            // so each object is numbered.
+            let stem = source.file_stem().unwrap().to_string_lossy();
+            let object = target.join(format!("{}-{}-{}.o", package.name, objects.len(), stem));
+            compiler_settings.compile(source.clone(), object.clone())?;
+            objects.push(object);
+        }
+        for path in package.library_paths.iter() {
+            linker_settings.add_library_path(path.clone());
+        }
+        for library in package.libraries.iter() {
+            linker_settings.add_library(library.clone());
+        }
+    }
+
+    linker_settings.link(objects, target.join(&root.name))?;
+    std::println!("built `{}` from {}", root.name, MANIFEST_NAME);
+
+    Ok(())
+}
@@ -1,4 +1,34 @@
-use std::{path::PathBuf, process::Command, vec::Vec};
+use std::{path::PathBuf, process::Command, string::String, vec::Vec};
+
+// The runtime library is looked up in a `lib` directory next to the compiler
+// or above it, as in `bin/../lib` or `target/debug/../../lib`.
+pub fn runtime_library() -> anyhow::Result<PathBuf> {
+    let executable = std::env::current_exe()?;
+    executable
+        .ancestors()
+        .skip(1)
+        .map(|directory| directory.join("lib/libcrrt.a"))
+        .find(|library| library.is_file())
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "cannot find `lib/libcrrt.a` above `{}`",
+                executable.display()
+            )
+        })
+}
+
+// Runs a compiler or linker, failing unless it exits successfully.
+fn run(mut command: Command) -> anyhow::Result<()> {
+    let program = command.get_program().to_string_lossy().into_owned();
+    let exit_code = command
+        .spawn()
+        .and_then(|mut child| child.wait())
+        .map_err(|error| anyhow::anyhow!("cannot run `{}`: {}", program, error))?;
+    if !exit_code.success() {
+        return Err(anyhow::anyhow!("`{}` failed with {}", program, exit_code));
+    }
+    Ok(())
+}
 
 pub enum CCompilerType {
     GCC,
@@ -29,7 +59,7 @@ impl CompilerSettings {
         self
     }
 
-    pub fn compile(&self, file: PathBuf, output: PathBuf) {
+    pub fn compile(&self, file: PathBuf, output: PathBuf) -> anyhow::Result<()> {
         let compiler_path = if let Some(path) = &self.path {
             path.clone()
         } else {
@@ -37,7 +67,7 @@ impl CompilerSettings {
                 CCompilerType::GCC => PathBuf::from("gcc"),
                 CCompilerType::Clang => PathBuf::from("clang"),
                 CCompilerType::Unknown => {
-                    panic!("No Compiler!");
+                    return Err(anyhow::anyhow!("no C compiler is set"));
                 }
             }
         };
@@ -51,9 +81,7 @@ impl CompilerSettings {
 
         std::println!("CC: {:?}", command);
 
-        let mut child = command.spawn().unwrap();
-        let exit_code = child.wait().unwrap();
-        assert!(exit_code.success());
+        run(command)
     }
 }
 
@@ -67,6 +95,8 @@ pub enum LinkerType {
 pub struct LinkerSettings {
     pub path: Option<PathBuf>,
     pub linker_type: LinkerType,
+    pub libraries: Vec<String>,
+    pub library_paths: Vec<PathBuf>,
 }
 
 impl LinkerSettings {
@@ -74,6 +104,8 @@ impl LinkerSettings {
         Self {
             path: None,
             linker_type: LinkerType::Unknown,
+            libraries: Vec::new(),
+            library_paths: Vec::new(),
         }
     }
 
@@ -87,7 +119,17 @@ impl LinkerSettings {
         self
     }
 
-    pub fn link(&self, files: Vec<PathBuf>, output: PathBuf) {
+    pub fn add_library(&mut self, library: String) -> &mut Self {
+        self.libraries.push(library);
+        self
+    }
+
+    pub fn add_library_path(&mut self, path: PathBuf) -> &mut Self {
+        self.library_paths.push(path);
+        self
+    }
+
+    pub fn link(&self, files: Vec<PathBuf>, output: PathBuf) -> anyhow::Result<()> {
         let linker_path = if let Some(path) = &self.path {
             path.clone()
         } else {
@@ -96,7 +138,7 @@ impl LinkerSettings {
                 LinkerType::Lld => PathBuf::from("lld"),
                 LinkerType::Mold => PathBuf::from("mold"),
                 LinkerType::Unknown => {
-                    panic!("No Linker!");
+                    return Err(anyhow::anyhow!("no linker is set"));
                 }
             }
         };
@@ -105,13 +147,17 @@ impl LinkerSettings {
         for file in files {
             command.arg(file);
         }
+        for path in self.library_paths.iter() {
+            command.arg("-L").arg(path);
+        }
+        for library in self.libraries.iter() {
+            command.arg(std::format!("-l{}", library));
+        }
         command.arg("-o");
         command.arg(output);
 
         std::println!("LD: {:?}", command);
 
-        let mut child = command.spawn().unwrap();
-        let exit_code = child.wait().unwrap();
-        assert!(exit_code.success());
+        run(command)
     }
 }
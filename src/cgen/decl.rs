@@ -29,11 +29,14 @@ impl Display for CConst {
             }
             write!(f, ") = {};", self.value)
         } else {
+            // The qualifier goes on the name, so a constant pointer can still
+            // write to what it points to.
             write!(
                 f,
-                "const {} {} = {};",
-                self.value.get_type(),
-                self.name,
+                "{} = {};",
+                self.value
+                    .get_type()
+                    .declarator(&alloc::format!("const {}", self.name)),
                 self.value
             )
         }
@@ -107,7 +110,7 @@ impl Display for CVariable {
             }
             write!(f, ") = {};", self.value)
         } else {
-            write!(f, "{} {} = {};", self.type_, self.name, self.value)
+            write!(f, "{} = {};", self.type_.declarator(&self.name), self.value)
         }
     }
 }
@@ -1,4 +1,6 @@
-use alloc::string::String;
+use alloc::{boxed::Box, string::String};
+
+use crate::ast::BinaryOp;
 
 use super::*;
 
@@ -48,7 +50,7 @@ impl Display for CFunction {
             write!(f, "{} {}(", self.return_type, self.name)?;
 
             for (i, (ty, name)) in self.params.iter().enumerate() {
-                write!(f, "{} {}", ty, name)?;
+                write!(f, "{}", ty.declarator(name))?;
 
                 if i < self.params.len() - 1 {
                     write!(f, ", ")?;
@@ -64,7 +66,7 @@ impl Display for CFunction {
             write!(f, "{} {}(", self.return_type, self.name)?;
 
             for (i, (ty, name)) in self.params.iter().enumerate() {
-                write!(f, "{} {}", ty, name)?;
+                write!(f, "{}", ty.declarator(name))?;
 
                 if i < self.params.len() - 1 {
                     write!(f, ", ")?;
@@ -124,6 +126,14 @@ impl Display for CBlockItem {
 
 pub enum CStatement {
     Return(CReturn),
+    If(CIf),
+    While(CWhile),
+    For(Box<CFor>),
+    Switch(CSwitch),
+    Assign(CAssign),
+    Break,
+    Continue,
+    Goto(String),
 }
 
 impl Display for CStatement {
@@ -136,6 +146,14 @@ impl Display for CStatement {
                     write!(f, "return;")
                 }
             }
+            Self::If(r#if) => write!(f, "{}", r#if),
+            Self::While(r#while) => write!(f, "{}", r#while),
+            Self::For(r#for) => write!(f, "{}", r#for),
+            Self::Switch(switch) => write!(f, "{}", switch),
+            Self::Assign(assign) => write!(f, "{}", assign),
+            Self::Break => write!(f, "break;"),
+            Self::Continue => write!(f, "continue;"),
+            Self::Goto(label) => write!(f, "goto {};", label),
         }
     }
 }
@@ -149,3 +167,213 @@ impl CReturn {
         Self { value }
     }
 }
+
+pub struct CIf {
+    condition: Value,
+    then_block: CBlock,
+    else_block: Option<CBlock>,
+}
+
+impl CIf {
+    pub fn new(condition: Value, then_block: CBlock, else_block: Option<CBlock>) -> Self {
+        Self {
+            condition,
+            then_block,
+            else_block,
+        }
+    }
+}
+
+impl Display for CIf {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "if ({}) {{\n{}}}", self.condition, self.then_block)?;
+
+        if let Some(else_block) = &self.else_block {
+            write!(f, " else {{\n{}}}", else_block)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub struct CWhile {
+    condition: Value,
+    body: CBlock,
+    break_label: Option<String>,
+    continue_label: Option<String>,
+}
+
+impl CWhile {
+    pub fn new(condition: Value, body: CBlock) -> Self {
+        Self {
+            condition,
+            body,
+            break_label: None,
+            continue_label: None,
+        }
+    }
+
+    pub fn set_break_label(&mut self, label: String) {
+        self.break_label = Some(label);
+    }
+
+    pub fn set_continue_label(&mut self, label: String) {
+        self.continue_label = Some(label);
+    }
+}
+
+impl Display for CWhile {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "while ({}) {{\n{}", self.condition, self.body)?;
+
+        if let Some(label) = &self.continue_label {
+            writeln!(f, "{}: ;", label)?;
+        }
+
+        write!(f, "}}")?;
+
+        if let Some(label) = &self.break_label {
+            write!(f, "\n{}: ;", label)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub struct CFor {
+    var: CIdentifier,
+    start: Value,
+    end: CIdentifier,
+    end_value: Value,
+    body: CBlock,
+    break_label: Option<String>,
+    continue_label: Option<String>,
+}
+
+impl CFor {
+    pub fn new(
+        var: CIdentifier,
+        start: Value,
+        end: CIdentifier,
+        end_value: Value,
+        body: CBlock,
+    ) -> Self {
+        Self {
+            var,
+            start,
+            end,
+            end_value,
+            body,
+            break_label: None,
+            continue_label: None,
+        }
+    }
+
+    pub fn set_break_label(&mut self, label: String) {
+        self.break_label = Some(label);
+    }
+
+    pub fn set_continue_label(&mut self, label: String) {
+        self.continue_label = Some(label);
+    }
+}
+
+impl Display for CFor {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let var = self.var.get_name();
+        let end = self.end.get_name();
+
+        write!(
+            f,
+            "for ({} {} = {}, {} = {}; {} < {}; {}++) {{\n{}",
+            self.var.get_type(),
+            var,
+            self.start,
+            end,
+            self.end_value,
+            var,
+            end,
+            var,
+            self.body
+        )?;
+
+        if let Some(label) = &self.continue_label {
+            writeln!(f, "{}: ;", label)?;
+        }
+
+        write!(f, "}}")?;
+
+        if let Some(label) = &self.break_label {
+            write!(f, "\n{}: ;", label)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub struct CSwitch {
+    value: Value,
+    cases: Vec<CCase>,
+    default: Option<CBlock>,
+}
+
+impl CSwitch {
+    pub fn new(value: Value, cases: Vec<CCase>, default: Option<CBlock>) -> Self {
+        Self {
+            value,
+            cases,
+            default,
+        }
+    }
+}
+
+impl Display for CSwitch {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        writeln!(f, "switch ({}) {{", self.value)?;
+
+        for case in self.cases.iter() {
+            for value in case.values.iter() {
+                writeln!(f, "case {}:", value)?;
+            }
+            writeln!(f, "{{\n{}break;\n}}", case.block)?;
+        }
+
+        if let Some(default) = &self.default {
+            writeln!(f, "default:\n{{\n{}break;\n}}", default)?;
+        }
+
+        write!(f, "}}")
+    }
+}
+
+pub struct CCase {
+    values: Vec<Value>,
+    block: CBlock,
+}
+
+impl CCase {
+    pub fn new(values: Vec<Value>, block: CBlock) -> Self {
+        Self { values, block }
+    }
+}
+
+pub struct CAssign {
+    target: Value,
+    op: Option<BinaryOp>,
+    value: Value,
+}
+
+impl CAssign {
+    pub fn new(target: Value, op: Option<BinaryOp>, value: Value) -> Self {
+        Self { target, op, value }
+    }
+}
+
+impl Display for CAssign {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match &self.op {
+            Some(op) => write!(f, "{} {}= {};", self.target, op, self.value),
+            None => write!(f, "{} = {};", self.target, self.value),
+        }
+    }
+}
@@ -1,6 +1,9 @@
 use core::fmt::Display;
 
-use alloc::{string::String, vec::Vec};
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
 
 mod decl;
 mod func;
@@ -15,12 +18,43 @@ pub use types::*;
 pub use values::*;
 
 pub struct CProgram {
+    types: Vec<CType>,
+    slice_types: Vec<CSliceType>,
     items: Vec<CDeclaration>,
+    bounds_checks: bool,
 }
 
 impl CProgram {
     pub fn new() -> Self {
-        Self { items: Vec::new() }
+        Self {
+            types: Vec::new(),
+            slice_types: Vec::new(),
+            items: Vec::new(),
+            bounds_checks: true,
+        }
+    }
+
+    pub fn set_bounds_checks(&mut self, bounds_checks: bool) {
+        self.bounds_checks = bounds_checks;
+    }
+
+    // Field types are resolved before the struct itself is declared, so
+    // insertion order is already a valid dependency order.
+    pub fn insert_type(&mut self, type_: CType) {
+        self.types.push(type_);
+    }
+
+    // Every slice type used by the program gets one struct definition,
+    // however many times it is spelled.
+    pub fn insert_slice_type(&mut self, slice_type: CSliceType) {
+        let name = slice_type.to_string();
+        if !self
+            .slice_types
+            .iter()
+            .any(|slice| slice.to_string() == name)
+        {
+            self.slice_types.push(slice_type);
+        }
     }
 
     pub fn insert_function(&mut self, function: CFunction) {
@@ -65,6 +99,74 @@ impl Display for CProgram {
         writeln!(f, "typedef unsigned short u16;")?;
         writeln!(f, "typedef unsigned int u32;")?;
         writeln!(f, "typedef unsigned long long u64;\n")?;
+        writeln!(f, "typedef float f32;")?;
+        writeln!(f, "typedef double f64;\n")?;
+        for type_ in self.types.iter() {
+            if let Some(backing_type) = type_.backing_type() {
+                writeln!(f, "typedef {} {};", backing_type, type_)?;
+            }
+        }
+        let records = self
+            .types
+            .iter()
+            .filter(|type_| type_.is_struct() || type_.is_union());
+        for record in records.clone() {
+            writeln!(f, "typedef struct {0} {0};", record)?;
+        }
+        for slice in self.slice_types.iter() {
+            writeln!(f, "typedef struct {0} {0};", slice)?;
+        }
+        for slice in self.slice_types.iter() {
+            let element_pointer = CType::new_pointer(CPointerType::new(slice.get_element_type()));
+            writeln!(f, "struct {} {{", slice)?;
+            writeln!(f, "{};", element_pointer.declarator("ptr"))?;
+            writeln!(f, "u64 len;")?;
+            writeln!(f, "}};\n")?;
+        }
+        for record in records {
+            writeln!(f, "struct {} {{", record)?;
+            if record.is_union() {
+                writeln!(f, "u32 {};", UNION_TAG)?;
+                writeln!(f, "union {{")?;
+            }
+            for (name, type_) in record.fields() {
+                // Variants without a payload only occupy a tag value.
+                if !type_.is_void() {
+                    writeln!(f, "{};", type_.declarator(&name))?;
+                }
+            }
+            if record.is_union() {
+                writeln!(f, "}};")?;
+            }
+            writeln!(f, "}};\n")?;
+        }
+        // Indexing and slicing go through these helpers so the slice is only
+        // evaluated once.
+        for slice in self.slice_types.iter() {
+            let element_pointer = CType::new_pointer(CPointerType::new(slice.get_element_type()));
+            writeln!(
+                f,
+                "static inline {} {{",
+                element_pointer.declarator(&alloc::format!("{}_at({} s, u64 index)", slice, slice))
+            )?;
+            if self.bounds_checks {
+                writeln!(f, "if (index >= s.len) __builtin_trap();")?;
+            }
+            writeln!(f, "return &s.ptr[index];\n}}")?;
+            writeln!(
+                f,
+                "static inline {0} {0}_sub({0} s, u64 start, u64 end) {{",
+                slice
+            )?;
+            if self.bounds_checks {
+                writeln!(f, "if (start > end || end > s.len) __builtin_trap();")?;
+            }
+            writeln!(
+                f,
+                "return ({}){{ s.ptr + start, end - start }};\n}}\n",
+                slice
+            )?;
+        }
         for item in self.items.iter() {
             writeln!(f, "{}", item)?;
         }
@@ -33,6 +33,10 @@ impl SymbolTable {
         self.stack.push_front(symbol);
     }
 
+    pub fn iter(&self) -> impl Iterator<Item = &Symbol> {
+        self.stack.iter().rev()
+    }
+
     pub fn get(&self, name: &str) -> Option<&Symbol> {
         self.stack.iter().find(|symbol| match symbol {
             Symbol::Const(n, _) => n == name,
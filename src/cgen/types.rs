@@ -9,6 +9,10 @@ use super::*;
 
 use anyhow::Result;
 
+// Tagged unions lower to a struct whose first member holds the index of the
+// active variant.
+pub const UNION_TAG: &str = "_cara_tag";
+
 #[derive(Debug, Clone)]
 pub struct CFunctionType {
     param_types: Vec<CType>,
@@ -55,13 +59,195 @@ impl CIntType {
     pub fn new(bit_width: u32, signed: bool) -> Self {
         Self { bit_width, signed }
     }
+
+    pub fn bit_width(&self) -> u32 {
+        self.bit_width
+    }
+
+    pub fn signed(&self) -> bool {
+        self.signed
+    }
+
+    pub fn min(&self) -> i128 {
+        if self.signed {
+            -(1i128 << (self.bit_width - 1))
+        } else {
+            0
+        }
+    }
+
+    pub fn max(&self) -> i128 {
+        if self.signed {
+            (1i128 << (self.bit_width - 1)) - 1
+        } else {
+            (1i128 << self.bit_width) - 1
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CFloatType {
+    bit_width: u32,
+}
+
+impl CFloatType {
+    pub fn new(bit_width: u32) -> Self {
+        Self { bit_width }
+    }
+
+    pub fn bit_width(&self) -> u32 {
+        self.bit_width
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CArrayType {
+    element_type: Box<CType>,
+    len: u64,
+}
+
+impl CArrayType {
+    pub fn new(element_type: CType, len: u64) -> Self {
+        Self {
+            element_type: Box::new(element_type),
+            len,
+        }
+    }
+
+    pub fn get_element_type(&self) -> CType {
+        *self.element_type.clone()
+    }
+
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+}
+
+// Constant pointees are wrapped in `Const`, volatile ones only change how the
+// pointer is spelled since C qualifies the accesses through it.
+#[derive(Debug, Clone)]
+pub struct CPointerType {
+    pointee: Box<CType>,
+    volatile: bool,
+}
+
+impl CPointerType {
+    pub fn new(pointee: CType) -> Self {
+        Self {
+            pointee: Box::new(pointee),
+            volatile: false,
+        }
+    }
+
+    pub fn set_volatile(&mut self, volatile: bool) {
+        self.volatile = volatile;
+    }
+
+    pub fn get_pointee(&self) -> CType {
+        *self.pointee.clone()
+    }
+}
+
+// Slices lower to a `{ T *ptr; u64 len; }` struct generated per element type.
+#[derive(Debug, Clone)]
+pub struct CSliceType {
+    element_type: Box<CType>,
+}
+
+impl CSliceType {
+    pub fn new(element_type: CType) -> Self {
+        Self {
+            element_type: Box::new(element_type),
+        }
+    }
+
+    pub fn get_element_type(&self) -> CType {
+        *self.element_type.clone()
+    }
+}
+
+impl Display for CSliceType {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "_cara_slice_{}", self.element_type.mangled_name())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CStructType {
+    name: String,
+}
+
+impl CStructType {
+    pub fn new(name: String) -> Self {
+        Self { name }
+    }
+
+    pub fn get_name(&self) -> String {
+        self.name.clone()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CUnionType {
+    name: String,
+}
+
+impl CUnionType {
+    pub fn new(name: String) -> Self {
+        Self { name }
+    }
+
+    pub fn get_name(&self) -> String {
+        self.name.clone()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CEnumType {
+    name: String,
+    backing_type: CIntType,
+}
+
+impl CEnumType {
+    pub fn new(name: String, backing_type: CIntType) -> Self {
+        Self { name, backing_type }
+    }
+
+    pub fn get_name(&self) -> String {
+        self.name.clone()
+    }
+}
+
+// An imported file, whose symbols are its public declarations.
+#[derive(Debug, Clone)]
+pub struct CModuleType {
+    name: String,
+}
+
+impl CModuleType {
+    pub fn new(name: String) -> Self {
+        Self { name }
+    }
+
+    pub fn get_name(&self) -> String {
+        self.name.clone()
+    }
 }
 
 #[derive(Debug, Clone)]
 enum TypeEnum {
     Void,
+    Bool,
     Function(CFunctionType),
     Int(CIntType),
+    Float(CFloatType),
+    Array(CArrayType),
+    Pointer(CPointerType),
+    Slice(CSliceType),
+    Struct(CStructType),
+    Enum(CEnumType),
+    Union(CUnionType),
+    Module(CModuleType),
     Type,
     Const(Box<CType>),
 }
@@ -80,6 +266,13 @@ impl CType {
         }
     }
 
+    pub fn new_bool() -> Self {
+        Self {
+            type_enum: TypeEnum::Bool,
+            symbols: SymbolTable::new(),
+        }
+    }
+
     pub fn new_type() -> Self {
         Self {
             type_enum: TypeEnum::Type,
@@ -87,6 +280,13 @@ impl CType {
         }
     }
 
+    pub fn new_module(module_type: CModuleType, symbols: SymbolTable) -> Self {
+        Self {
+            type_enum: TypeEnum::Module(module_type),
+            symbols,
+        }
+    }
+
     pub fn new_function(function_type: CFunctionType) -> Self {
         Self {
             type_enum: TypeEnum::Function(function_type),
@@ -101,6 +301,97 @@ impl CType {
         }
     }
 
+    pub fn new_float(float_type: CFloatType) -> Self {
+        Self {
+            type_enum: TypeEnum::Float(float_type),
+            symbols: SymbolTable::new(),
+        }
+    }
+
+    pub fn new_array(array_type: CArrayType) -> Self {
+        Self {
+            type_enum: TypeEnum::Array(array_type),
+            symbols: SymbolTable::new(),
+        }
+    }
+
+    pub fn new_pointer(pointer_type: CPointerType) -> Self {
+        Self {
+            type_enum: TypeEnum::Pointer(pointer_type),
+            symbols: SymbolTable::new(),
+        }
+    }
+
+    pub fn new_slice(slice_type: CSliceType) -> Self {
+        let mut symbols = SymbolTable::new();
+        for (name, type_) in [
+            (
+                "ptr",
+                CType::new_pointer(CPointerType::new(slice_type.get_element_type())),
+            ),
+            ("len", CType::new_int(CIntType::new(64, false))),
+        ] {
+            symbols.push(Symbol::Var(
+                name.into(),
+                Value::new_identifier(CIdentifier::new(name.into(), type_)),
+            ));
+        }
+
+        Self {
+            type_enum: TypeEnum::Slice(slice_type),
+            symbols,
+        }
+    }
+
+    pub fn new_struct(struct_type: CStructType, fields: Vec<(String, CType)>) -> Self {
+        let mut symbols = SymbolTable::new();
+        for (name, type_) in fields {
+            symbols.push(Symbol::Var(
+                name.clone(),
+                Value::new_identifier(CIdentifier::new(name, type_)),
+            ));
+        }
+
+        Self {
+            type_enum: TypeEnum::Struct(struct_type),
+            symbols,
+        }
+    }
+
+    pub fn new_union(union_type: CUnionType, variants: Vec<(String, CType)>) -> Self {
+        let mut symbols = SymbolTable::new();
+        for (name, type_) in variants {
+            symbols.push(Symbol::Var(
+                name.clone(),
+                Value::new_identifier(CIdentifier::new(name, type_)),
+            ));
+        }
+
+        Self {
+            type_enum: TypeEnum::Union(union_type),
+            symbols,
+        }
+    }
+
+    pub fn new_enum(enum_type: CEnumType, variants: Vec<(String, u64)>) -> Self {
+        let mut symbols = SymbolTable::new();
+        for (name, value) in variants {
+            symbols.push(Symbol::Const(
+                name,
+                Value::new_int(
+                    value,
+                    enum_type.backing_type.bit_width(),
+                    enum_type.backing_type.signed(),
+                ),
+            ));
+        }
+
+        Self {
+            type_enum: TypeEnum::Enum(enum_type),
+            symbols,
+        }
+    }
+
     pub fn new_const(type_: CType) -> Self {
         Self {
             type_enum: TypeEnum::Const(Box::new(type_)),
@@ -111,21 +402,333 @@ impl CType {
     pub fn get_name(&self) -> String {
         match &self.type_enum {
             TypeEnum::Void => "void".to_string(),
+            TypeEnum::Bool => "bool".to_string(),
             TypeEnum::Function(_) => "function".to_string(),
             TypeEnum::Int(_) => "int".to_string(),
+            TypeEnum::Float(_) => "float".to_string(),
+            TypeEnum::Array(array_type) => {
+                alloc::format!(
+                    "[{}]{}",
+                    array_type.len,
+                    array_type.element_type.element_name()
+                )
+            }
+            TypeEnum::Pointer(pointer_type) if pointer_type.volatile => {
+                alloc::format!("*volatile {}", pointer_type.pointee.element_name())
+            }
+            TypeEnum::Pointer(pointer_type) => {
+                alloc::format!("*{}", pointer_type.pointee.element_name())
+            }
+            TypeEnum::Slice(slice_type) => {
+                alloc::format!("[]{}", slice_type.element_type.element_name())
+            }
+            TypeEnum::Struct(struct_type) => struct_type.get_name(),
+            TypeEnum::Enum(enum_type) => enum_type.get_name(),
+            TypeEnum::Union(union_type) => union_type.get_name(),
+            TypeEnum::Module(module_type) => alloc::format!("module {}", module_type.get_name()),
             TypeEnum::Type => "type".to_string(),
             TypeEnum::Const(type_) => alloc::format!("const {}", type_.get_name()),
         }
     }
+
+    // Element types keep their width, otherwise `[]i32` and `[]i64` would
+    // both read `[]int`.
+    fn element_name(&self) -> String {
+        match &self.type_enum {
+            TypeEnum::Int(_) | TypeEnum::Float(_) => self.to_string(),
+            TypeEnum::Const(type_) => alloc::format!("const {}", type_.element_name()),
+            _ => self.get_name(),
+        }
+    }
 }
 
 impl CType {
     pub fn is_const(&self) -> bool {
+        matches!(&self.type_enum, TypeEnum::Const(_))
+    }
+
+    // The type a copy of a value of this type has.
+    pub fn without_const(&self) -> CType {
         match &self.type_enum {
-            TypeEnum::Const(_) => true,
+            TypeEnum::Const(type_) => type_.without_const(),
+            _ => self.clone(),
+        }
+    }
+
+    pub fn is_void(&self) -> bool {
+        match &self.type_enum {
+            TypeEnum::Void => true,
+            TypeEnum::Const(type_) => type_.is_void(),
             _ => false,
         }
     }
+
+    pub fn is_bool(&self) -> bool {
+        match &self.type_enum {
+            TypeEnum::Bool => true,
+            TypeEnum::Const(type_) => type_.is_bool(),
+            _ => false,
+        }
+    }
+
+    pub fn is_int(&self) -> bool {
+        match &self.type_enum {
+            TypeEnum::Int(_) => true,
+            TypeEnum::Const(type_) => type_.is_int(),
+            _ => false,
+        }
+    }
+
+    pub fn is_float(&self) -> bool {
+        match &self.type_enum {
+            TypeEnum::Float(_) => true,
+            TypeEnum::Const(type_) => type_.is_float(),
+            _ => false,
+        }
+    }
+
+    pub fn is_array(&self) -> bool {
+        self.array_type().is_some()
+    }
+
+    fn array_type(&self) -> Option<&CArrayType> {
+        match &self.type_enum {
+            TypeEnum::Array(array_type) => Some(array_type),
+            TypeEnum::Const(type_) => type_.array_type(),
+            _ => None,
+        }
+    }
+
+    pub fn is_pointer(&self) -> bool {
+        self.pointer_type().is_some()
+    }
+
+    fn pointer_type(&self) -> Option<&CPointerType> {
+        match &self.type_enum {
+            TypeEnum::Pointer(pointer_type) => Some(pointer_type),
+            TypeEnum::Const(type_) => type_.pointer_type(),
+            _ => None,
+        }
+    }
+
+    pub fn is_slice(&self) -> bool {
+        self.slice_type().is_some()
+    }
+
+    fn slice_type(&self) -> Option<&CSliceType> {
+        match &self.type_enum {
+            TypeEnum::Slice(slice_type) => Some(slice_type),
+            TypeEnum::Const(type_) => type_.slice_type(),
+            _ => None,
+        }
+    }
+
+    pub fn is_struct(&self) -> bool {
+        self.struct_type().is_some()
+    }
+
+    fn struct_type(&self) -> Option<&CStructType> {
+        match &self.type_enum {
+            TypeEnum::Struct(struct_type) => Some(struct_type),
+            TypeEnum::Const(type_) => type_.struct_type(),
+            _ => None,
+        }
+    }
+
+    pub fn is_enum(&self) -> bool {
+        self.enum_type().is_some()
+    }
+
+    fn enum_type(&self) -> Option<&CEnumType> {
+        match &self.type_enum {
+            TypeEnum::Enum(enum_type) => Some(enum_type),
+            TypeEnum::Const(type_) => type_.enum_type(),
+            _ => None,
+        }
+    }
+
+    pub fn is_type(&self) -> bool {
+        matches!(self.type_enum, TypeEnum::Type)
+    }
+
+    pub fn is_module(&self) -> bool {
+        matches!(self.type_enum, TypeEnum::Module(_))
+    }
+
+    pub fn is_union(&self) -> bool {
+        match &self.type_enum {
+            TypeEnum::Union(_) => true,
+            TypeEnum::Const(type_) => type_.is_union(),
+            _ => false,
+        }
+    }
+
+    // Structs, enums and unions are only compatible with themselves.
+    fn nominal_name(&self) -> Option<String> {
+        match &self.type_enum {
+            TypeEnum::Struct(struct_type) => Some(struct_type.get_name()),
+            TypeEnum::Enum(enum_type) => Some(enum_type.get_name()),
+            TypeEnum::Union(union_type) => Some(union_type.get_name()),
+            TypeEnum::Const(type_) => type_.nominal_name(),
+            _ => None,
+        }
+    }
+
+    // A C identifier fragment naming the type, used for generated slice
+    // structs and their helpers.
+    fn mangled_name(&self) -> String {
+        match &self.type_enum {
+            TypeEnum::Void => "void".into(),
+            TypeEnum::Bool => "bool".into(),
+            TypeEnum::Function(_) => "fn".into(),
+            TypeEnum::Type => "type".into(),
+            TypeEnum::Module(_) => "module".into(),
+            TypeEnum::Int(_) | TypeEnum::Float(_) => self.to_string(),
+            TypeEnum::Array(array_type) => alloc::format!(
+                "a{}_{}",
+                array_type.len,
+                array_type.element_type.mangled_name()
+            ),
+            TypeEnum::Pointer(pointer_type) => alloc::format!(
+                "p{}_{}",
+                if pointer_type.volatile { "v" } else { "" },
+                pointer_type.pointee.mangled_name()
+            ),
+            TypeEnum::Slice(slice_type) => {
+                alloc::format!("s_{}", slice_type.element_type.mangled_name())
+            }
+            TypeEnum::Struct(struct_type) => struct_type.get_name(),
+            TypeEnum::Enum(enum_type) => enum_type.get_name(),
+            TypeEnum::Union(union_type) => union_type.get_name(),
+            TypeEnum::Const(type_) => alloc::format!("c_{}", type_.mangled_name()),
+        }
+    }
+
+    // Pointers to the same type, whatever their qualifiers, can be compared
+    // and subtracted.
+    pub fn is_compatible_pointer(&self, other: &CType) -> bool {
+        let unqualified = |pointer_type: &CPointerType| match &pointer_type.pointee.type_enum {
+            TypeEnum::Const(type_) => type_.mangled_name(),
+            _ => pointer_type.pointee.mangled_name(),
+        };
+
+        match (self.pointer_type(), other.pointer_type()) {
+            (Some(lhs), Some(rhs)) => unqualified(lhs) == unqualified(rhs),
+            _ => false,
+        }
+    }
+
+    // The value of a discriminant, which is stored as a `u64` even when the
+    // backing type is signed.
+    pub fn discriminant(&self, value: u64) -> i128 {
+        match self.enum_type() {
+            Some(enum_type) if enum_type.backing_type.signed() => value as i64 as i128,
+            _ => value as i128,
+        }
+    }
+
+    pub fn backing_type(&self) -> Option<CType> {
+        self.enum_type()
+            .map(|enum_type| CType::new_int(enum_type.backing_type.clone()))
+    }
+}
+
+impl CType {
+    // Names an anonymous struct, enum or union after the first constant it is
+    // bound to.
+    pub fn set_type_name(&mut self, name: String) -> bool {
+        match &mut self.type_enum {
+            TypeEnum::Struct(struct_type) if struct_type.name.is_empty() => {
+                struct_type.name = name;
+                true
+            }
+            TypeEnum::Enum(enum_type) if enum_type.name.is_empty() => {
+                enum_type.name = name;
+                true
+            }
+            TypeEnum::Union(union_type) if union_type.name.is_empty() => {
+                union_type.name = name;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn variant(&self, name: &str, span: Span) -> Result<Value> {
+        // Modules are accessed the same way, as in `io.print`.
+        if let TypeEnum::Module(module_type) = &self.type_enum {
+            return match self.symbols.get(name) {
+                Some(Symbol::Const(_, value)) | Some(Symbol::Var(_, value)) => Ok(value.clone()),
+                _ => Err(
+                    CompileError::new_no_export(span, module_type.get_name(), name.into()).into(),
+                ),
+            };
+        }
+
+        if self.is_enum()
+            && let Some(Symbol::Const(_, value)) = self.symbols.get(name)
+        {
+            return Ok(Value::new_enum_variant(value.as_int(span)?, self.clone()));
+        }
+
+        // Variants without a payload can be named directly, like enum variants.
+        if self.is_union()
+            && let Some((tag, payload)) = self.union_variant(name)
+        {
+            if !payload.is_void() {
+                return Err(
+                    CompileError::new_missing_payload(span, self.get_name(), name.into()).into(),
+                );
+            }
+
+            return Ok(Value::new_struct(CStructLiteral::new(
+                self.clone(),
+                alloc::vec![(UNION_TAG.into(), Value::new_int(tag, 32, false))],
+            )));
+        }
+
+        Err(CompileError::new_no_variant(span, self.get_name(), name.into()).into())
+    }
+
+    pub fn fields(&self) -> Vec<(String, CType)> {
+        match &self.type_enum {
+            TypeEnum::Const(type_) => type_.fields(),
+            _ => self
+                .symbols
+                .iter()
+                .filter_map(|symbol| match symbol {
+                    Symbol::Var(name, value) => Some((name.clone(), value.get_type())),
+                    _ => None,
+                })
+                .collect(),
+        }
+    }
+
+    pub fn field(&self, name: &str, span: Span) -> Result<CType> {
+        if (self.is_struct() || self.is_slice())
+            && let Some((_, type_)) = self.fields().into_iter().find(|(field, _)| field == name)
+        {
+            return Ok(type_);
+        }
+
+        Err(CompileError::new_no_field(span, self.get_name(), name.into()).into())
+    }
+
+    pub fn union_variant(&self, name: &str) -> Option<(u64, CType)> {
+        if !self.is_union() {
+            return None;
+        }
+
+        self.fields()
+            .into_iter()
+            .enumerate()
+            .find(|(_, (variant, _))| variant == name)
+            .map(|(tag, (_, type_))| (tag as u64, type_))
+    }
+
+    pub fn get_symbol(&self, name: &str) -> Option<&Symbol> {
+        self.symbols.get(name)
+    }
 }
 
 impl CType {
@@ -141,6 +744,143 @@ impl CType {
     }
 }
 
+impl CType {
+    pub fn as_int(&self, span: Span) -> Result<CIntType> {
+        match &self.type_enum {
+            TypeEnum::Int(int_type) => Ok(int_type.clone()),
+            TypeEnum::Const(type_) => type_.as_int(span),
+            _ => {
+                Err(CompileError::new_invalid_type_cast(span, self.get_name(), "int".into()).into())
+            }
+        }
+    }
+}
+
+impl CType {
+    pub fn as_float(&self, span: Span) -> Result<CFloatType> {
+        match &self.type_enum {
+            TypeEnum::Float(float_type) => Ok(float_type.clone()),
+            TypeEnum::Const(type_) => type_.as_float(span),
+            _ => Err(
+                CompileError::new_invalid_type_cast(span, self.get_name(), "float".into()).into(),
+            ),
+        }
+    }
+}
+
+impl CType {
+    pub fn as_array(&self, span: Span) -> Result<CArrayType> {
+        match self.array_type() {
+            Some(array_type) => Ok(array_type.clone()),
+            None => {
+                Err(CompileError::new_mismatched_type(span, "array".into(), self.get_name()).into())
+            }
+        }
+    }
+}
+
+impl CType {
+    pub fn as_pointer(&self, span: Span) -> Result<CPointerType> {
+        match self.pointer_type() {
+            Some(pointer_type) => Ok(pointer_type.clone()),
+            None => Err(
+                CompileError::new_mismatched_type(span, "pointer".into(), self.get_name()).into(),
+            ),
+        }
+    }
+}
+
+impl CType {
+    pub fn as_slice(&self, span: Span) -> Result<CSliceType> {
+        match self.slice_type() {
+            Some(slice_type) => Ok(slice_type.clone()),
+            None => {
+                Err(CompileError::new_mismatched_type(span, "slice".into(), self.get_name()).into())
+            }
+        }
+    }
+}
+
+impl CType {
+    pub fn as_bool(&self, span: Span) -> Result<()> {
+        match &self.type_enum {
+            TypeEnum::Bool => Ok(()),
+            TypeEnum::Const(type_) => type_.as_bool(span),
+            _ => {
+                Err(CompileError::new_mismatched_type(span, "bool".into(), self.get_name()).into())
+            }
+        }
+    }
+}
+
+impl CType {
+    // `as` converts between numbers, bools, enums and their integers, and
+    // pointers and their addresses. C rejects casts of aggregates.
+    pub fn check_cast(&self, to: &CType, span: Span) -> Result<()> {
+        let number = |type_: &CType| type_.is_int() || type_.is_float();
+        let integer = |type_: &CType| type_.is_int() || type_.is_bool() || type_.is_enum();
+        let valid = number(self) && number(to)
+            || integer(self) && integer(to) && !(self.is_enum() && to.is_enum())
+            || (self.is_int() || self.is_pointer()) && to.is_pointer()
+            || self.is_pointer() && to.is_int();
+        if !valid {
+            return Err(
+                CompileError::new_invalid_type_cast(span, self.get_name(), to.get_name()).into(),
+            );
+        }
+
+        Ok(())
+    }
+
+    pub fn check_assignable(&self, from: &CType, span: Span) -> Result<()> {
+        if let (Some(to_array), Some(from_array)) = (self.array_type(), from.array_type())
+            && to_array.len == from_array.len
+        {
+            return to_array
+                .element_type
+                .check_assignable(&from_array.element_type, span);
+        }
+
+        // Slices and pointers may gain `const` on what they point to but
+        // never lose it.
+        let targets = match (self.slice_type(), from.slice_type()) {
+            (Some(to_slice), Some(from_slice)) => {
+                Some((&to_slice.element_type, &from_slice.element_type))
+            }
+            _ => self
+                .pointer_type()
+                .zip(from.pointer_type())
+                .filter(|(to_pointer, from_pointer)| to_pointer.volatile || !from_pointer.volatile)
+                .map(|(to_pointer, from_pointer)| (&to_pointer.pointee, &from_pointer.pointee)),
+        };
+        if let Some((to_target, from_target)) = targets
+            && (to_target.mangled_name() == from_target.mangled_name()
+                || matches!(&to_target.type_enum, TypeEnum::Const(type_)
+                    if type_.mangled_name() == from_target.mangled_name()))
+        {
+            return Ok(());
+        }
+
+        if self.is_bool() != from.is_bool()
+            || self.is_array()
+            || from.is_array()
+            || self.is_slice()
+            || from.is_slice()
+            || self.is_pointer()
+            || from.is_pointer()
+            || self.is_void() != from.is_void()
+            || (self.is_int() && from.is_float())
+            || self.nominal_name() != from.nominal_name()
+        {
+            return Err(
+                CompileError::new_mismatched_type(span, self.get_name(), from.get_name()).into(),
+            );
+        }
+
+        Ok(())
+    }
+}
+
 impl CType {
     pub fn function_type(&self, paramter_types: Vec<CType>, span: Span) -> Result<Self> {
         let mut paramter_types_list = Vec::new();
@@ -154,6 +894,15 @@ impl CType {
                 CFunctionType::new(paramter_types_list, Self::new_int(int_type.clone()))
             }
             TypeEnum::Void => CFunctionType::new(paramter_types_list, Self::new_void()),
+            TypeEnum::Bool => CFunctionType::new(paramter_types_list, Self::new_bool()),
+            TypeEnum::Float(float_type) => {
+                CFunctionType::new(paramter_types_list, Self::new_float(float_type.clone()))
+            }
+            TypeEnum::Struct(_)
+            | TypeEnum::Enum(_)
+            | TypeEnum::Union(_)
+            | TypeEnum::Slice(_)
+            | TypeEnum::Pointer(_) => CFunctionType::new(paramter_types_list, self.clone()),
             _ => return Err(CompileError::new_non_comptime_value(span, self.get_name()).into()),
         };
 
@@ -161,6 +910,50 @@ impl CType {
     }
 }
 
+impl CType {
+    // C spells array types around the declared name, so `[2][3]i32 m` becomes
+    // `i32 m[2][3]`.
+    pub fn declarator(&self, name: &str) -> String {
+        match &self.type_enum {
+            TypeEnum::Array(array_type) => {
+                array_type
+                    .element_type
+                    .declarator(&alloc::format!("{}[{}]", name, array_type.len))
+            }
+            // Pointers to arrays need parentheses to bind before the brackets,
+            // and qualifiers then apply to the elements.
+            TypeEnum::Pointer(pointer_type) if pointer_type.pointee.is_array() => {
+                let declarator = pointer_type
+                    .pointee
+                    .declarator(&alloc::format!("(*{})", name));
+                if pointer_type.volatile {
+                    alloc::format!("volatile {}", declarator)
+                } else {
+                    declarator
+                }
+            }
+            // Qualifiers go right after the type they apply to, which also
+            // works when that type is itself a pointer.
+            TypeEnum::Pointer(pointer_type) => {
+                let mut declarator = alloc::format!("*{}", name);
+                if pointer_type.volatile {
+                    declarator = alloc::format!("volatile {}", declarator);
+                }
+                match &pointer_type.pointee.type_enum {
+                    TypeEnum::Const(type_) => {
+                        type_.declarator(&alloc::format!("const {}", declarator))
+                    }
+                    _ => pointer_type.pointee.declarator(&declarator),
+                }
+            }
+            TypeEnum::Const(type_) if type_.is_array() => {
+                alloc::format!("const {}", type_.declarator(name))
+            }
+            _ => alloc::format!("{} {}", self, name),
+        }
+    }
+}
+
 impl Display for CType {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match &self.type_enum {
@@ -172,7 +965,16 @@ impl Display for CType {
                     write!(f, "u{}", int_type.bit_width)
                 }
             }
+            TypeEnum::Float(float_type) => write!(f, "f{}", float_type.bit_width),
+            TypeEnum::Array(_) => write!(f, "{}", self.declarator("")),
+            TypeEnum::Pointer(_) => write!(f, "{}", self.declarator("")),
+            TypeEnum::Slice(slice_type) => write!(f, "{}", slice_type),
+            TypeEnum::Struct(struct_type) => write!(f, "{}", struct_type.name),
+            TypeEnum::Enum(enum_type) => write!(f, "{}", enum_type.name),
+            TypeEnum::Union(union_type) => write!(f, "{}", union_type.name),
             TypeEnum::Void => write!(f, "void"),
+            TypeEnum::Bool => write!(f, "_Bool"),
+            TypeEnum::Module(module_type) => write!(f, "{}", module_type.name),
             TypeEnum::Type => write!(f, "type"),
             TypeEnum::Const(type_) => write!(f, "const {}", type_),
         }
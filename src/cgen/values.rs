@@ -1,10 +1,11 @@
-use crate::ast::{BinaryOp, Span};
+use crate::ast::{BinaryOp, Span, UnaryOp};
 
 use super::*;
 use crate::backend::CompileError;
 use alloc::{
     boxed::Box,
     string::{String, ToString},
+    vec::Vec,
 };
 use anyhow::Result;
 
@@ -36,12 +37,23 @@ impl CIdentifier {
 enum ValueEnum {
     Identyfier(CIdentifier),
     Int(u64),
+    Float(f64),
+    Bool(bool),
+    Str(String),
     BinOp(BinOp),
+    UnaryOp(UnOp),
     Type(CType),
     Void,
     GetAddr(CGetAddr),
     ConvertType(CConvertType),
     Call(CCall),
+    Member(CMember),
+    Struct(CStructLiteral),
+    Array(CArray),
+    Index(CIndex),
+    SliceIndex(CSliceIndex),
+    SubSlice(CSubSlice),
+    Deref(CDeref),
 }
 
 #[derive(Debug, Clone)]
@@ -60,8 +72,8 @@ impl Value {
 
     pub fn new_call(call: CCall) -> Self {
         Self {
+            value_type: call.type_.clone(),
             value_enum: ValueEnum::Call(call),
-            value_type: CType::new_void(),
         }
     }
 
@@ -79,10 +91,74 @@ impl Value {
         }
     }
 
+    pub fn new_enum_variant(value: u64, enum_type: CType) -> Self {
+        Self {
+            value_enum: ValueEnum::Int(value),
+            value_type: enum_type,
+        }
+    }
+
+    pub fn new_float(value: f64, bit_width: u32) -> Self {
+        Self {
+            value_enum: ValueEnum::Float(value),
+            value_type: CType::new_float(CFloatType::new(bit_width)),
+        }
+    }
+
+    pub fn new_bool(value: bool) -> Self {
+        Self {
+            value_enum: ValueEnum::Bool(value),
+            value_type: CType::new_bool(),
+        }
+    }
+
+    // String literals are null-terminated `*const u8` unless they are
+    // coerced to a `[]const u8`.
+    pub fn new_str(value: String) -> Self {
+        let byte = CType::new_const(CType::new_int(CIntType::new(8, false)));
+        Self {
+            value_enum: ValueEnum::Str(value),
+            value_type: CType::new_pointer(CPointerType::new(byte)),
+        }
+    }
+
     pub fn new_bin_op(bin_op: BinOp) -> Self {
+        let value_type = match bin_op.op {
+            BinaryOp::Lt
+            | BinaryOp::Gt
+            | BinaryOp::Le
+            | BinaryOp::Ge
+            | BinaryOp::Eq
+            | BinaryOp::Neq
+            | BinaryOp::And
+            | BinaryOp::Or => CType::new_bool(),
+            BinaryOp::Sub
+                if bin_op.lhs.get_type().is_pointer() && bin_op.rhs.get_type().is_pointer() =>
+            {
+                CType::new_int(CIntType::new(64, true))
+            }
+            _ if bin_op.rhs.get_type().is_float() && !bin_op.lhs.get_type().is_float() => {
+                bin_op.rhs.get_type()
+            }
+            _ if bin_op.rhs.get_type().is_pointer() => bin_op.rhs.get_type(),
+            _ => bin_op.lhs.get_type(),
+        };
+
+        Self {
+            value_enum: ValueEnum::BinOp(bin_op),
+            value_type,
+        }
+    }
+
+    pub fn new_unary_op(unary_op: UnOp) -> Self {
+        let value_type = match unary_op.op {
+            UnaryOp::Not => CType::new_bool(),
+            _ => unary_op.value.get_type(),
+        };
+
         Self {
-            value_enum: ValueEnum::BinOp(bin_op.clone()),
-            value_type: bin_op.lhs.get_type(),
+            value_enum: ValueEnum::UnaryOp(unary_op),
+            value_type,
         }
     }
 
@@ -95,8 +171,57 @@ impl Value {
 
     pub fn new_get_addr(get_addr: CGetAddr) -> Self {
         Self {
+            value_type: CType::new_pointer(CPointerType::new(get_addr.value.get_type())),
             value_enum: ValueEnum::GetAddr(get_addr),
-            value_type: CType::new_type(),
+        }
+    }
+
+    pub fn new_member(member: CMember) -> Self {
+        Self {
+            value_type: member.type_.clone(),
+            value_enum: ValueEnum::Member(member),
+        }
+    }
+
+    pub fn new_struct(struct_literal: CStructLiteral) -> Self {
+        Self {
+            value_type: struct_literal.type_.clone(),
+            value_enum: ValueEnum::Struct(struct_literal),
+        }
+    }
+
+    pub fn new_array(array: CArray) -> Self {
+        Self {
+            value_type: array.type_.clone(),
+            value_enum: ValueEnum::Array(array),
+        }
+    }
+
+    pub fn new_index(index: CIndex) -> Self {
+        Self {
+            value_type: index.type_.clone(),
+            value_enum: ValueEnum::Index(index),
+        }
+    }
+
+    pub fn new_deref(deref: CDeref) -> Self {
+        Self {
+            value_type: deref.type_.clone(),
+            value_enum: ValueEnum::Deref(deref),
+        }
+    }
+
+    pub fn new_slice_index(index: CSliceIndex) -> Self {
+        Self {
+            value_type: index.type_.clone(),
+            value_enum: ValueEnum::SliceIndex(index),
+        }
+    }
+
+    pub fn new_sub_slice(sub_slice: CSubSlice) -> Self {
+        Self {
+            value_type: CType::new_slice(sub_slice.slice_type.clone()),
+            value_enum: ValueEnum::SubSlice(sub_slice),
         }
     }
 
@@ -134,6 +259,163 @@ impl Value {
     }
 }
 
+impl Value {
+    pub fn as_type(&self, span: Span) -> Result<CType> {
+        match &self.value_enum {
+            ValueEnum::Type(type_) => Ok(type_.clone()),
+            _ => Err(CompileError::new_mismatched_type(
+                span,
+                "type".to_string(),
+                self.value_type.get_name(),
+            )
+            .into()),
+        }
+    }
+}
+
+impl Value {
+    // Literals can be repeated in C without being evaluated again.
+    pub fn is_literal(&self) -> bool {
+        match &self.value_enum {
+            ValueEnum::Int(_) | ValueEnum::Float(_) | ValueEnum::Bool(_) | ValueEnum::Str(_) => {
+                true
+            }
+            ValueEnum::UnaryOp(un_op) => un_op.value.is_literal(),
+            _ => false,
+        }
+    }
+
+    pub fn is_array_literal(&self) -> bool {
+        matches!(self.value_enum, ValueEnum::Array(_))
+    }
+
+    // Values that name an object in memory and so can have their address
+    // taken.
+    pub fn is_lvalue(&self) -> bool {
+        matches!(
+            self.value_enum,
+            ValueEnum::Identyfier(_)
+                | ValueEnum::Member(_)
+                | ValueEnum::Index(_)
+                | ValueEnum::SliceIndex(_)
+                | ValueEnum::Deref(_)
+        )
+    }
+
+    // Whether the value is part of a variable rather than reached through a
+    // pointer or slice.
+    pub fn is_direct(&self) -> bool {
+        match &self.value_enum {
+            ValueEnum::Identyfier(_) => true,
+            ValueEnum::Member(member) => member.value.is_direct(),
+            ValueEnum::Index(index) => index.value.get_type().is_array() && index.value.is_direct(),
+            _ => false,
+        }
+    }
+
+    // C arrays cannot be copied, so an array value can only initialize an
+    // object when it is spelled out as a literal.
+    pub fn check_initializer(&self, span: Span) -> Result<()> {
+        if self.value_type.is_array() && !self.is_array_literal() {
+            return Err(CompileError::new_array_copy(span).into());
+        }
+
+        Ok(())
+    }
+
+    // Outside of initializers an array literal has to be written as a
+    // compound literal of the expected type.
+    pub fn into_compound_literal(self, type_: CType) -> Value {
+        match self.value_enum {
+            ValueEnum::Array(mut array) => {
+                array.compound = Some(type_);
+                Value::new_array(array)
+            }
+            _ => self,
+        }
+    }
+
+    // String literals are `*const u8` unless a `[]const u8` is expected, and
+    // integer literals have to fit the expected type.
+    pub fn coerce(self, type_: &CType, span: Span) -> Result<Value> {
+        self.check_int_literal(type_, span.clone())?;
+        self.check_float_literal(type_, span)?;
+
+        let byte = CType::new_const(CType::new_int(CIntType::new(8, false)));
+        let byte_slice = CType::new_slice(CSliceType::new(byte));
+        Ok(match &self.value_enum {
+            ValueEnum::Str(value) if type_.to_string() == byte_slice.to_string() => {
+                let len = Value::new_int(value.len() as u64, 64, false);
+                Value::new_struct(CStructLiteral::new(
+                    byte_slice,
+                    alloc::vec![("ptr".into(), self), ("len".into(), len)],
+                ))
+            }
+            _ => self,
+        })
+    }
+
+    // The value of an integer literal, looking through negation.
+    pub fn int_literal(&self) -> Option<i128> {
+        match &self.value_enum {
+            ValueEnum::Int(val) if !self.value_type.is_enum() => Some(*val as i128),
+            ValueEnum::UnaryOp(un_op) if matches!(un_op.op, UnaryOp::Negative) => {
+                un_op.value.int_literal().map(|val| -val)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn check_int_literal(&self, type_: &CType, span: Span) -> Result<()> {
+        let (Some(val), Ok(int_type)) = (self.int_literal(), type_.as_int(span.clone())) else {
+            return Ok(());
+        };
+
+        if val < int_type.min() || val > int_type.max() {
+            return Err(CompileError::new_literal_overflow(
+                span,
+                val.to_string(),
+                type_.to_string(),
+            )
+            .into());
+        }
+
+        Ok(())
+    }
+}
+
+impl Value {
+    // The value of a float literal, looking through negation.
+    pub fn float_literal(&self) -> Option<f64> {
+        match &self.value_enum {
+            ValueEnum::Float(val) => Some(*val),
+            ValueEnum::UnaryOp(un_op) if matches!(un_op.op, UnaryOp::Negative) => {
+                un_op.value.float_literal().map(|val| -val)
+            }
+            _ => None,
+        }
+    }
+
+    // Float literals are `f64`, so only `f32` can be too small for them.
+    pub fn check_float_literal(&self, type_: &CType, span: Span) -> Result<()> {
+        let (Some(val), Ok(float_type)) = (self.float_literal(), type_.as_float(span.clone()))
+        else {
+            return Ok(());
+        };
+
+        if float_type.bit_width() == 32 && (val as f32).is_infinite() {
+            return Err(CompileError::new_literal_overflow(
+                span,
+                alloc::format!("{:?}", val),
+                type_.to_string(),
+            )
+            .into());
+        }
+
+        Ok(())
+    }
+}
+
 impl Value {
     pub fn get_type(&self) -> CType {
         self.value_type.clone()
@@ -144,13 +426,47 @@ impl Display for Value {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match &self.value_enum {
             ValueEnum::Identyfier(identifier) => write!(f, "{}", identifier.get_name()),
+            ValueEnum::Int(val) if self.value_type.is_enum() => {
+                write!(
+                    f,
+                    "(({}){})",
+                    self.value_type,
+                    self.value_type.discriminant(*val)
+                )
+            }
+            // Without the suffix C would warn that the constant is unsigned.
+            ValueEnum::Int(val) if *val > i64::MAX as u64 => write!(f, "{}u", val),
             ValueEnum::Int(val) => write!(f, "{}", val),
+            ValueEnum::Float(val) => write!(f, "{:?}", val),
+            ValueEnum::Bool(val) => write!(f, "{}", *val as u8),
+            // Bytes outside printable ASCII are written as octal escapes,
+            // which unlike `\x` cannot run into the following character.
+            // The cast turns C's `char *` into the byte pointer type.
+            ValueEnum::Str(val) => {
+                write!(f, "(({})\"", self.value_type)?;
+                for byte in val.bytes() {
+                    match byte {
+                        b'"' | b'\\' => write!(f, "\\{}", byte as char)?,
+                        b' '..=b'~' => write!(f, "{}", byte as char)?,
+                        _ => write!(f, "\\{:03o}", byte)?,
+                    }
+                }
+                write!(f, "\")")
+            }
             ValueEnum::BinOp(bin_op) => write!(f, "{}", bin_op),
+            ValueEnum::UnaryOp(unary_op) => write!(f, "{}", unary_op),
             ValueEnum::Void => write!(f, "void"),
             ValueEnum::Type(type_) => write!(f, "{}", type_),
             ValueEnum::GetAddr(get_addr) => write!(f, "{}", get_addr),
             ValueEnum::ConvertType(convert_type) => write!(f, "{}", convert_type),
             ValueEnum::Call(call) => write!(f, "{}", call),
+            ValueEnum::Member(member) => write!(f, "{}", member),
+            ValueEnum::Struct(struct_literal) => write!(f, "{}", struct_literal),
+            ValueEnum::Array(array) => write!(f, "{}", array),
+            ValueEnum::Index(index) => write!(f, "{}", index),
+            ValueEnum::SliceIndex(index) => write!(f, "{}", index),
+            ValueEnum::SubSlice(sub_slice) => write!(f, "{}", sub_slice),
+            ValueEnum::Deref(deref) => write!(f, "{}", deref),
         }
     }
 }
@@ -172,8 +488,231 @@ impl BinOp {
     }
 }
 
+impl BinOp {
+    pub fn check(&self, lhs_span: Span, rhs_span: Span) -> Result<()> {
+        for (value, span) in [(&self.lhs, &lhs_span), (&self.rhs, &rhs_span)] {
+            if value.get_type().is_struct()
+                || value.get_type().is_union()
+                || value.get_type().is_slice()
+            {
+                return Err(CompileError::new_invalid_operand(
+                    span.clone(),
+                    self.op.to_string(),
+                    value.get_type().to_string(),
+                )
+                .into());
+            }
+        }
+
+        if self.lhs.get_type().is_pointer() || self.rhs.get_type().is_pointer() {
+            return self.check_pointer(lhs_span, rhs_span);
+        }
+
+        // Bools can only be compared for equality and combined, which also
+        // rejects chained comparisons like `a < b < c`.
+        if self.lhs.get_type().is_bool() || self.rhs.get_type().is_bool() {
+            if let BinaryOp::Eq | BinaryOp::Neq | BinaryOp::And | BinaryOp::Or = self.op {
+                return self
+                    .lhs
+                    .get_type()
+                    .check_assignable(&self.rhs.get_type(), rhs_span);
+            }
+
+            let (value, span) = if self.lhs.get_type().is_bool() {
+                (&self.lhs, lhs_span)
+            } else {
+                (&self.rhs, rhs_span)
+            };
+            return Err(CompileError::new_invalid_operand(
+                span,
+                self.op.to_string(),
+                value.get_type().to_string(),
+            )
+            .into());
+        }
+
+        // Enums can only be compared with values of the same enum.
+        if self.lhs.get_type().is_enum() || self.rhs.get_type().is_enum() {
+            if let BinaryOp::Eq
+            | BinaryOp::Neq
+            | BinaryOp::Lt
+            | BinaryOp::Gt
+            | BinaryOp::Le
+            | BinaryOp::Ge = self.op
+            {
+                return self
+                    .lhs
+                    .get_type()
+                    .check_assignable(&self.rhs.get_type(), rhs_span);
+            }
+
+            let (value, span) = if self.lhs.get_type().is_enum() {
+                (&self.lhs, lhs_span)
+            } else {
+                (&self.rhs, rhs_span)
+            };
+            return Err(CompileError::new_invalid_operand(
+                span,
+                self.op.to_string(),
+                value.get_type().to_string(),
+            )
+            .into());
+        }
+
+        if let BinaryOp::Mod
+        | BinaryOp::BitAnd
+        | BinaryOp::BitOr
+        | BinaryOp::BitXor
+        | BinaryOp::Shl
+        | BinaryOp::Shr = self.op
+        {
+            for (value, span) in [(&self.lhs, &lhs_span), (&self.rhs, &rhs_span)] {
+                if value.get_type().is_float() {
+                    return Err(CompileError::new_invalid_operand(
+                        span.clone(),
+                        self.op.to_string(),
+                        value.get_type().to_string(),
+                    )
+                    .into());
+                }
+            }
+        }
+
+        // A literal operand has to fit the type of the other side, except for
+        // shift amounts which are checked against the width instead.
+        if !matches!(self.op, BinaryOp::Shl | BinaryOp::Shr) {
+            self.lhs
+                .check_int_literal(&self.rhs.get_type(), lhs_span.clone())?;
+            self.rhs
+                .check_int_literal(&self.lhs.get_type(), rhs_span.clone())?;
+            self.lhs
+                .check_float_literal(&self.rhs.get_type(), lhs_span)?;
+            self.rhs
+                .check_float_literal(&self.lhs.get_type(), rhs_span.clone())?;
+        }
+
+        self.check_shift_amount(rhs_span)
+    }
+
+    // Pointers can be offset by an integer, subtracted from and compared with
+    // pointers to the same type, and nothing else.
+    fn check_pointer(&self, lhs_span: Span, rhs_span: Span) -> Result<()> {
+        let (lhs_type, rhs_type) = (self.lhs.get_type(), self.rhs.get_type());
+        let invalid = |value: &Value, span: Span| {
+            Err(CompileError::new_invalid_operand(
+                span,
+                self.op.to_string(),
+                value.get_type().to_string(),
+            )
+            .into())
+        };
+
+        match self.op {
+            BinaryOp::Add | BinaryOp::Sub if lhs_type.is_pointer() && rhs_type.is_int() => {
+                self.check_pointer_offset(&self.lhs, lhs_span)
+            }
+            BinaryOp::Add if lhs_type.is_int() && rhs_type.is_pointer() => {
+                self.check_pointer_offset(&self.rhs, rhs_span)
+            }
+            BinaryOp::Sub
+            | BinaryOp::Eq
+            | BinaryOp::Neq
+            | BinaryOp::Lt
+            | BinaryOp::Gt
+            | BinaryOp::Le
+            | BinaryOp::Ge
+                if lhs_type.is_pointer() && rhs_type.is_pointer() =>
+            {
+                if !lhs_type.is_compatible_pointer(&rhs_type) {
+                    return Err(CompileError::new_mismatched_type(
+                        rhs_span,
+                        lhs_type.get_name(),
+                        rhs_type.get_name(),
+                    )
+                    .into());
+                }
+                if let BinaryOp::Sub = self.op {
+                    return self.check_pointer_offset(&self.lhs, lhs_span);
+                }
+                Ok(())
+            }
+            _ if lhs_type.is_pointer() => invalid(&self.lhs, lhs_span),
+            _ => invalid(&self.rhs, rhs_span),
+        }
+    }
+
+    // Offsets are scaled by the pointee size, which `void` does not have.
+    fn check_pointer_offset(&self, pointer: &Value, span: Span) -> Result<()> {
+        if pointer
+            .get_type()
+            .as_pointer(span.clone())?
+            .get_pointee()
+            .is_void()
+        {
+            return Err(CompileError::new_invalid_operand(
+                span,
+                self.op.to_string(),
+                pointer.get_type().to_string(),
+            )
+            .into());
+        }
+
+        Ok(())
+    }
+
+    fn check_shift_amount(&self, span: Span) -> Result<()> {
+        let (BinaryOp::Shl | BinaryOp::Shr) = self.op else {
+            return Ok(());
+        };
+        let (Ok(int_type), Some(amount)) = (
+            self.lhs.get_type().as_int(span.clone()),
+            self.rhs.int_literal(),
+        ) else {
+            return Ok(());
+        };
+
+        if amount < 0 {
+            return Err(CompileError::new_negative_shift(span, amount).into());
+        }
+        if amount >= int_type.bit_width() as i128 {
+            return Err(CompileError::new_shift_overflow(
+                span,
+                amount as u64,
+                self.lhs.get_type().to_string(),
+            )
+            .into());
+        }
+
+        Ok(())
+    }
+
+    fn signed_lhs(&self) -> Option<CIntType> {
+        self.lhs
+            .get_type()
+            .as_int(Span::new((0, 0), (0, 0), "".into(), "".into()))
+            .ok()
+            .filter(|int_type| int_type.signed())
+    }
+}
+
 impl Display for BinOp {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        // Shifts on signed integers use two's complement semantics. `<<` shifts
+        // the unsigned representation so that moving bits into or out of the
+        // sign bit is well defined, and `>>` is an arithmetic shift that copies
+        // the sign bit, which is how both GCC and Clang implement it.
+        if let (BinaryOp::Shl, Some(int_type)) = (&self.op, self.signed_lhs()) {
+            let unsigned = CType::new_int(CIntType::new(int_type.bit_width(), false));
+            return write!(
+                f,
+                "(({})(({}){} << {}))",
+                self.lhs.get_type(),
+                unsigned,
+                self.lhs,
+                self.rhs
+            );
+        }
+
         write!(f, "({} {} {})", self.lhs, self.op, self.rhs)
     }
 }
@@ -192,6 +731,52 @@ impl Display for BinaryOp {
             BinaryOp::Le => write!(f, "<="),
             BinaryOp::Gt => write!(f, ">"),
             BinaryOp::Ge => write!(f, ">="),
+            BinaryOp::BitAnd => write!(f, "&"),
+            BinaryOp::BitOr => write!(f, "|"),
+            BinaryOp::BitXor => write!(f, "^"),
+            BinaryOp::Shl => write!(f, "<<"),
+            BinaryOp::Shr => write!(f, ">>"),
+            BinaryOp::And => write!(f, "&&"),
+            BinaryOp::Or => write!(f, "||"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UnOp {
+    value: Box<Value>,
+    op: UnaryOp,
+}
+
+impl UnOp {
+    pub fn new(value: Value, op: UnaryOp) -> Self {
+        Self {
+            value: Box::new(value),
+            op,
+        }
+    }
+}
+
+impl Display for UnOp {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        // C has no signed type for `9223372036854775808`, so negating it
+        // would give an unsigned constant.
+        if matches!(self.op, UnaryOp::Negative)
+            && self.value.int_literal() == Some(-(i64::MIN as i128))
+        {
+            return write!(f, "(-9223372036854775807LL - 1)");
+        }
+        write!(f, "({}{})", self.op, self.value)
+    }
+}
+
+impl Display for UnaryOp {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            UnaryOp::Positive => write!(f, "+"),
+            UnaryOp::Negative => write!(f, "-"),
+            UnaryOp::Not => write!(f, "!"),
+            UnaryOp::BitNot => write!(f, "~"),
         }
     }
 }
@@ -215,6 +800,27 @@ impl Display for CGetAddr {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct CDeref {
+    value: Box<Value>,
+    type_: CType,
+}
+
+impl CDeref {
+    pub fn new(value: Value, type_: CType) -> Self {
+        Self {
+            value: Box::new(value),
+            type_,
+        }
+    }
+}
+
+impl Display for CDeref {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "(*{})", self.value)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct CConvertType {
     value: Box<Value>,
@@ -240,13 +846,16 @@ impl Display for CConvertType {
 pub struct CCall {
     value: Box<Value>,
     args: Vec<Value>,
+    // The return type of the function called.
+    type_: CType,
 }
 
 impl CCall {
-    pub fn new(value: Value, args: Vec<Value>) -> Self {
+    pub fn new(value: Value, args: Vec<Value>, type_: CType) -> Self {
         Self {
             value: Box::new(value),
             args,
+            type_,
         }
     }
 }
@@ -266,3 +875,198 @@ impl Display for CCall {
         write!(f, ")")
     }
 }
+
+#[derive(Debug, Clone)]
+pub struct CMember {
+    value: Box<Value>,
+    field: String,
+    type_: CType,
+}
+
+impl CMember {
+    pub fn new(value: Value, field: String, type_: CType) -> Self {
+        Self {
+            value: Box::new(value),
+            field,
+            type_,
+        }
+    }
+}
+
+impl Display for CMember {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}.{}", self.value, self.field)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CStructLiteral {
+    type_: CType,
+    fields: Vec<(String, Value)>,
+}
+
+impl CStructLiteral {
+    pub fn new(type_: CType, fields: Vec<(String, Value)>) -> Self {
+        Self { type_, fields }
+    }
+}
+
+impl Display for CStructLiteral {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "({}){{", self.type_)?;
+        for (i, (name, value)) in self.fields.iter().enumerate() {
+            write!(f, " .{} = {}", name, value)?;
+            if i < self.fields.len() - 1 {
+                write!(f, ",")?;
+            }
+        }
+        write!(f, " }}")
+    }
+}
+
+#[derive(Debug, Clone)]
+enum CArrayItems {
+    List(Vec<Value>),
+    Repeat(Box<Value>, u64),
+}
+
+#[derive(Debug, Clone)]
+pub struct CArray {
+    items: CArrayItems,
+    type_: CType,
+    compound: Option<CType>,
+}
+
+impl CArray {
+    pub fn new_list(items: Vec<Value>, element_type: CType) -> Self {
+        Self {
+            type_: CType::new_array(CArrayType::new(element_type, items.len() as u64)),
+            items: CArrayItems::List(items),
+            compound: None,
+        }
+    }
+
+    pub fn new_repeat(item: Value, len: u64) -> Self {
+        Self {
+            type_: CType::new_array(CArrayType::new(item.get_type(), len)),
+            items: CArrayItems::Repeat(Box::new(item), len),
+            compound: None,
+        }
+    }
+}
+
+impl Display for CArray {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if let Some(type_) = &self.compound {
+            write!(f, "({})", type_)?;
+        }
+
+        match &self.items {
+            CArrayItems::List(items) => {
+                write!(f, "{{")?;
+                for (i, item) in items.iter().enumerate() {
+                    write!(f, "{}", item)?;
+                    if i < items.len() - 1 {
+                        write!(f, ", ")?;
+                    }
+                }
+                write!(f, "}}")
+            }
+            // Range designators are a GNU extension, so every element is
+            // written out.
+            CArrayItems::Repeat(item, len) => {
+                write!(f, "{{")?;
+                for i in 0..*len {
+                    write!(f, "{}", item)?;
+                    if i < len - 1 {
+                        write!(f, ", ")?;
+                    }
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CIndex {
+    value: Box<Value>,
+    index: Box<Value>,
+    type_: CType,
+}
+
+impl CIndex {
+    pub fn new(value: Value, index: Value, type_: CType) -> Self {
+        Self {
+            value: Box::new(value),
+            index: Box::new(index),
+            type_,
+        }
+    }
+}
+
+impl Display for CIndex {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}[{}]", self.value, self.index)
+    }
+}
+
+// Slice element access through the generated `_at` helper, which checks the
+// index in safe builds.
+#[derive(Debug, Clone)]
+pub struct CSliceIndex {
+    slice_type: CSliceType,
+    slice: Box<Value>,
+    index: Box<Value>,
+    type_: CType,
+}
+
+impl CSliceIndex {
+    pub fn new(slice_type: CSliceType, slice: Value, index: Value, type_: CType) -> Self {
+        Self {
+            slice_type,
+            slice: Box::new(slice),
+            index: Box::new(index),
+            type_,
+        }
+    }
+}
+
+impl Display for CSliceIndex {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "(*{}_at({}, {}))",
+            self.slice_type, self.slice, self.index
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CSubSlice {
+    slice_type: CSliceType,
+    slice: Box<Value>,
+    start: Box<Value>,
+    end: Box<Value>,
+}
+
+impl CSubSlice {
+    pub fn new(slice_type: CSliceType, slice: Value, start: Value, end: Value) -> Self {
+        Self {
+            slice_type,
+            slice: Box::new(slice),
+            start: Box::new(start),
+            end: Box::new(end),
+        }
+    }
+}
+
+impl Display for CSubSlice {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "{}_sub({}, {}, {})",
+            self.slice_type, self.slice, self.start, self.end
+        )
+    }
+}
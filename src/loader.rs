@@ -0,0 +1,215 @@
+use crate::{
+    ast::{CompUnit, Span},
+    backend::{CompileError, Module},
+    parser::CParser,
+};
+use std::{
+    format,
+    path::{Path, PathBuf},
+    string::{String, ToString},
+    vec::Vec,
+};
+
+// Reads a file and everything it imports. Modules come out after the modules
+// they import, with the root file last.
+pub struct Loader {
+    modules: Vec<(PathBuf, Module)>,
+    // The chain of files being loaded, to report import cycles.
+    loading: Vec<(PathBuf, String)>,
+    // For each package, the dependencies its files can import by name, with
+    // the entry file and package of each.
+    packages: Vec<Vec<(String, PathBuf, usize)>>,
+}
+
+impl Loader {
+    pub fn new() -> Self {
+        Self {
+            modules: Vec::new(),
+            loading: Vec::new(),
+            packages: Vec::new(),
+        }
+    }
+
+    pub fn add_package(&mut self, dependencies: Vec<(String, PathBuf, usize)>) -> usize {
+        self.packages.push(dependencies);
+        self.packages.len() - 1
+    }
+
+    pub fn load(mut self, path: &Path, package: Option<usize>) -> anyhow::Result<Vec<Module>> {
+        self.load_module(path, None, package)?;
+
+        let mut modules: Vec<Module> = self.modules.into_iter().map(|(_, module)| module).collect();
+        modules.last_mut().unwrap().prefix = None;
+        Ok(modules)
+    }
+
+    fn load_module(
+        &mut self,
+        path: &Path,
+        span: Option<Span>,
+        package: Option<usize>,
+    ) -> anyhow::Result<usize> {
+        let name = path.display().to_string();
+        let code = match (std::fs::read_to_string(path), span.clone()) {
+            (Ok(code), _) => code,
+            (Err(_), Some(span)) => {
+                return Err(CompileError::new_module_not_found(span, name).into());
+            }
+            (Err(error), None) => return Err(anyhow::anyhow!("{}: {}", name, error)),
+        };
+        let key = std::fs::canonicalize(path)?;
+
+        if let Some(index) = self.modules.iter().position(|(loaded, _)| *loaded == key) {
+            return Ok(index);
+        }
+        // Only imports can get back to a file that is still loading.
+        if let Some(start) = self.loading.iter().position(|(loading, _)| *loading == key) {
+            let mut cycle: Vec<&str> = self.loading[start..]
+                .iter()
+                .map(|(_, name)| name.as_str())
+                .collect();
+            cycle.push(&name);
+            return Err(CompileError::new_import_cycle(span.unwrap(), cycle.join(" -> ")).into());
+        }
+
+        self.loading.push((key.clone(), name.clone()));
+        let ast: CompUnit = CParser::new(code, name).parse();
+        let directory = path.parent().unwrap_or(Path::new(""));
+        let mut imports = Vec::new();
+        for import in ast.imports() {
+            if let Some(code) = import.path.invalid {
+                return Err(
+                    CompileError::new_invalid_code_point(import.path.span.clone(), code).into(),
+                );
+            }
+            let dependency = package.and_then(|package| {
+                self.packages[package]
+                    .iter()
+                    .find(|(name, _, _)| *name == import.path.value)
+            });
+            let (import_path, import_package) = match dependency {
+                Some((_, entry, dependency)) => (entry.clone(), Some(*dependency)),
+                None => (directory.join(&import.path.value), package),
+            };
+            let index =
+                self.load_module(&import_path, Some(import.span.clone()), import_package)?;
+            imports.push((import.path.value.clone(), index));
+        }
+        self.loading.pop();
+
+        let name = path.file_stem().unwrap().to_string_lossy().to_string();
+        let index = self.modules.len();
+        let prefix = format!(
+            "_cara_{}{}_",
+            name.replace(|c: char| !c.is_ascii_alphanumeric(), "_"),
+            index
+        );
+        self.modules.push((
+            key,
+            Module {
+                name,
+                prefix: Some(prefix),
+                imports,
+                ast,
+            },
+        ));
+
+        Ok(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::CompileErrorEnum;
+
+    // Writes `files` to a fresh directory and loads the first one.
+    fn load(test: &str, files: &[(&str, &str)]) -> anyhow::Result<Vec<Module>> {
+        let directory = std::env::temp_dir().join(format!("cara_{}_{}", test, std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        for (name, code) in files {
+            std::fs::write(directory.join(name), code).unwrap();
+        }
+        let modules = Loader::new().load(&directory.join(files[0].0), None);
+        std::fs::remove_dir_all(&directory).unwrap();
+        modules
+    }
+
+    fn load_error(test: &str, files: &[(&str, &str)]) -> CompileErrorEnum {
+        let Err(error) = load(test, files) else {
+            panic!("expected `{}` to fail to load", files[0].0);
+        };
+        error.downcast::<CompileError>().unwrap().error
+    }
+
+    fn compile_error(test: &str, files: &[(&str, &str)]) -> CompileErrorEnum {
+        let modules = load(test, files).unwrap();
+        let errors = crate::compile(&modules, true).unwrap_err();
+        errors.into_iter().next().unwrap().error
+    }
+
+    #[test]
+    fn missing_root_file() {
+        let path = std::env::temp_dir().join("cara_missing_root.cara");
+        assert!(Loader::new().load(&path, None).is_err());
+    }
+
+    #[test]
+    fn import_cycle() {
+        let error = load_error(
+            "import_cycle",
+            &[
+                ("a.cara", "const b = @import(\"b.cara\");"),
+                ("b.cara", "const c = @import(\"c.cara\");"),
+                ("c.cara", "const b = @import(\"b.cara\");"),
+            ],
+        );
+        let CompileErrorEnum::ImportCycle(cycle) = error else {
+            panic!("expected an import cycle, got {:?}", error);
+        };
+        let files: Vec<_> = cycle
+            .split(" -> ")
+            .map(|name| Path::new(name).file_name().unwrap())
+            .collect();
+        assert_eq!(files, ["b.cara", "c.cara", "b.cara"]);
+    }
+
+    #[test]
+    fn missing_module() {
+        let error = load_error(
+            "missing_module",
+            &[("a.cara", "const b = @import(\"b.cara\");")],
+        );
+        assert!(matches!(error, CompileErrorEnum::ModuleNotFound(_)));
+    }
+
+    #[test]
+    fn private_access() {
+        let files = [
+            (
+                "main.cara",
+                "const lib = @import(\"lib.cara\");
+                const main = fn() i32 { return lib.secret; };",
+            ),
+            ("lib.cara", "const secret = 1; pub const open = 2;"),
+        ];
+        assert_eq!(
+            compile_error("private_access", &files),
+            CompileErrorEnum::NoExport {
+                module: "lib".to_string(),
+                name: "secret".to_string(),
+            }
+        );
+
+        let files = [
+            (
+                "main.cara",
+                "const lib = @import(\"lib.cara\");
+                const main = fn() i32 { return lib.open; };",
+            ),
+            files[1],
+        ];
+        let modules = load("public_access", &files).unwrap();
+        assert!(crate::compile(&modules, true).is_ok());
+    }
+}
@@ -4,6 +4,8 @@
 #[cfg(feature = "std")]
 extern crate std;
 
+#[cfg(feature = "compile_c")]
+mod build;
 #[cfg(feature = "compile_c")]
 mod c_compile;
 
@@ -12,11 +14,15 @@ extern crate alloc;
 mod ast;
 mod backend;
 mod cgen;
+#[cfg(feature = "std")]
+mod loader;
+#[cfg(feature = "std")]
+mod manifest;
 mod parser;
 
 use std::fs::File;
 
-use alloc::string::{String, ToString};
+use alloc::string::String;
 use backend::Codegen;
 
 #[cfg(feature = "std")]
@@ -28,75 +34,181 @@ use {argh::FromArgs, std::borrow::ToOwned, std::format, std::vec, std::vec::Vec}
 struct CCPMArgs {
     #[argh(option, short = 'i')]
     #[argh(description = "the input source code")]
-    input_source: String,
+    input_source: Option<String>,
 
     #[argh(option, short = 'o')]
     #[argh(description = "the output file")]
-    output_source: String,
+    output_source: Option<String>,
+
+    #[argh(switch)]
+    #[argh(description = "do not check slice bounds at runtime")]
+    no_bounds_checks: bool,
+
+    #[argh(subcommand)]
+    command: Option<Command>,
+}
+
+#[cfg(feature = "std")]
+#[derive(FromArgs)]
+#[argh(subcommand)]
+enum Command {
+    Build(BuildArgs),
 }
 
+#[cfg(feature = "std")]
+#[derive(FromArgs)]
+#[argh(
+    subcommand,
+    name = "build",
+    description = "build the package in the current directory"
+)]
+struct BuildArgs {}
+
 fn main() {
     #[cfg(feature = "std")]
     let args = argh::from_env::<CCPMArgs>();
+
+    // Packages are always compiled and linked, which needs a C toolchain.
+    #[cfg(feature = "std")]
+    if let Some(Command::Build(_)) = &args.command {
+        #[cfg(feature = "compile_c")]
+        if let Err(error) = build::build(!args.no_bounds_checks) {
+            crate::error!("{}", error);
+            fail();
+        }
+        return;
+    }
+
+    #[cfg(feature = "std")]
+    let (Some(input_source), Some(output_source)) = (&args.input_source, &args.output_source)
+    else {
+        crate::error!("`-i` and `-o` are required unless a subcommand is given");
+        fail();
+        return;
+    };
+    // The object and executable are named after the C file, which would
+    // overwrite it without a `.c` extension.
+    #[cfg(feature = "compile_c")]
+    if std::path::Path::new(output_source).extension() != Some("c".as_ref()) {
+        crate::error!("`-o` should name a `.c` file");
+        fail();
+        return;
+    }
+
     #[cfg(feature = "std")]
-    let code = {
-        let mut input = File::open(args.input_source).unwrap();
-        let mut code = String::new();
-        std::io::Read::read_to_string(&mut input, &mut code).unwrap();
-        code
+    let modules = match loader::Loader::new().load(std::path::Path::new(input_source), None) {
+        Ok(modules) => modules,
+        Err(error) => {
+            crate::error!("{}", error);
+            fail();
+            return;
+        }
     };
 
     #[cfg(not(feature = "std"))]
-    let code = r#"
+    let modules = {
+        let code = r#"
     const main = fn () u64 {
         return 1+1-2;
     };
 
     "#;
 
-    let parser = parser::CParser::new(code.to_string(), "test.cara".to_string());
-    let ast = parser.parse();
-
-    let context = backend::CodegenContext::new();
-    let context = alloc::sync::Arc::new(spin::RwLock::new(context));
-    ast.codegen(context.clone()).unwrap();
+        let parser = parser::CParser::new(code.into(), "test.cara".into());
+        alloc::vec![backend::Module {
+            name: "test".into(),
+            prefix: None,
+            imports: alloc::vec::Vec::new(),
+            ast: parser.parse(),
+        }]
+    };
 
-    let context = context.read();
-    if context.errors.len() > 0 {
-        for error in context.errors.iter() {
-            crate::error!("{}", error);
-        }
+    #[cfg(feature = "std")]
+    let bounds_checks = !args.no_bounds_checks;
+    #[cfg(not(feature = "std"))]
+    let bounds_checks = true;
+    let Some(c_program) = generate(&modules, bounds_checks) else {
+        fail();
         return;
-    }
+    };
 
     #[cfg(feature = "std")]
     {
         use std::io::Write;
-        writeln!(
-            File::create(args.output_source.clone()).unwrap(),
-            "{}",
-            context.c_program
-        )
-        .unwrap();
+        if let Err(error) =
+            File::create(output_source).and_then(|mut file| writeln!(file, "{}", c_program))
+        {
+            crate::error!("cannot write `{}`: {}", output_source, error);
+            fail();
+            return;
+        }
 
         #[cfg(feature = "compile_c")]
         {
             use c_compile::*;
             use std::path::PathBuf;
 
+            // The object and executable are named after the C output.
+            let output = PathBuf::from(output_source);
+            let object = output.with_extension("o");
+            let executable = output.with_extension("");
+
             let mut compiler_settings = CompilerSettings::new();
             compiler_settings.set_compiler_type(CCompilerType::GCC);
-            compiler_settings.compile(
-                PathBuf::from(args.output_source.clone()),
-                PathBuf::from("test.o"),
-            );
+            if let Err(error) = compiler_settings.compile(output, object.clone()) {
+                crate::error!("{}", error);
+                fail();
+                return;
+            }
 
             let mut linker_settings = LinkerSettings::new();
             linker_settings.set_linker_type(LinkerType::Mold);
-            linker_settings.link(
-                vec![PathBuf::from("test.o"), PathBuf::from("lib/libcrrt.a")],
-                PathBuf::from("test"),
-            );
+            if let Err(error) = runtime_library()
+                .and_then(|runtime| linker_settings.link(vec![object, runtime], executable))
+            {
+                crate::error!("{}", error);
+                fail();
+            }
+        }
+    }
+}
+
+// Exits with a failure status once an error has been reported, so that make
+// and build scripts notice it.
+fn fail() {
+    #[cfg(feature = "std")]
+    std::process::exit(1);
+}
+
+// Generates the C program for modules ordered by their imports, or reports
+// the errors.
+fn generate(modules: &[backend::Module], bounds_checks: bool) -> Option<String> {
+    match compile(modules, bounds_checks) {
+        Ok(c_program) => Some(c_program),
+        Err(errors) => {
+            for error in errors.iter() {
+                crate::error!("{}", error);
+            }
+            None
         }
     }
 }
+
+fn compile(
+    modules: &[backend::Module],
+    bounds_checks: bool,
+) -> Result<String, alloc::vec::Vec<backend::CompileError>> {
+    let context = backend::CodegenContext::new();
+    let context = alloc::sync::Arc::new(spin::RwLock::new(context));
+    context.write().c_program.set_bounds_checks(bounds_checks);
+    for module in modules.iter() {
+        module.codegen(context.clone()).unwrap();
+    }
+
+    let mut context = context.write();
+    if !context.errors.is_empty() {
+        return Err(core::mem::take(&mut context.errors));
+    }
+
+    Ok(alloc::format!("{}", context.c_program))
+}
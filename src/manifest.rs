@@ -0,0 +1,305 @@
+use std::{
+    borrow::ToOwned,
+    format,
+    path::{Path, PathBuf},
+    string::{String, ToString},
+    vec::Vec,
+};
+use thiserror::Error;
+use toml::{Table, Value};
+
+pub const MANIFEST_NAME: &str = "Cara.toml";
+
+#[derive(Error, Debug)]
+pub enum ManifestError {
+    #[error("cannot read `{0}`")]
+    NotFound(String),
+    #[error("invalid manifest `{file}`:\n{error}")]
+    Syntax { file: String, error: String },
+    #[error("`{file}`: missing `{key}`")]
+    MissingKey { file: String, key: String },
+    #[error("`{file}`: unknown key `{key}`")]
+    UnknownKey { file: String, key: String },
+    #[error("`{file}`: `{key}` should be {expected}")]
+    InvalidValue {
+        file: String,
+        key: String,
+        expected: &'static str,
+    },
+}
+
+// A package as described by its `Cara.toml`, with paths relative to the
+// current directory.
+#[derive(Debug)]
+pub struct Manifest {
+    pub name: String,
+    pub root: PathBuf,
+    pub entry: PathBuf,
+    // Names the package's files can import, with the index of the package.
+    pub dependencies: Vec<(String, usize)>,
+    pub c_sources: Vec<PathBuf>,
+    pub libraries: Vec<String>,
+    pub library_paths: Vec<PathBuf>,
+}
+
+// Loads the package in `root` first, followed by everything it depends on.
+pub fn load_packages(root: &Path) -> Result<Vec<Manifest>, ManifestError> {
+    let mut packages = Vec::new();
+    load_package(root, &mut packages)?;
+    Ok(packages.into_iter().map(|(_, manifest)| manifest).collect())
+}
+
+fn load_package(
+    root: &Path,
+    packages: &mut Vec<(PathBuf, Manifest)>,
+) -> Result<usize, ManifestError> {
+    let file = root.join(MANIFEST_NAME);
+    let file_name = file.display().to_string();
+    let key =
+        std::fs::canonicalize(root).map_err(|_| ManifestError::NotFound(file_name.clone()))?;
+    if let Some(index) = packages.iter().position(|(loaded, _)| *loaded == key) {
+        return Ok(index);
+    }
+
+    let code =
+        std::fs::read_to_string(&file).map_err(|_| ManifestError::NotFound(file_name.clone()))?;
+    let toml: Table = code
+        .parse()
+        .map_err(|error: toml::de::Error| ManifestError::Syntax {
+            file: file_name.clone(),
+            error: error.to_string(),
+        })?;
+    let manifest = Manifest::from_toml(&toml, root, &file_name)?;
+
+    // Dependencies may depend on this package again, so it is registered
+    // before they are loaded.
+    let dependencies = match toml.get("dependencies") {
+        Some(Value::Table(entries)) => entries.clone(),
+        Some(_) => return Err(invalid(&file_name, "dependencies", "a table")),
+        None => Table::new(),
+    };
+    let index = packages.len();
+    packages.push((key, manifest));
+
+    let mut dependency_indices = Vec::new();
+    for (name, dependency) in dependencies.iter() {
+        let key = format!("dependencies.{}", name);
+        let path = match dependency {
+            Value::Table(dependency) => {
+                check_keys(dependency, &key, &["path"], &file_name)?;
+                dependency.get("path")
+            }
+            _ => None,
+        };
+        let Some(Value::String(path)) = path else {
+            return Err(invalid(&file_name, &key, "a table with a `path`"));
+        };
+        dependency_indices.push((name.clone(), load_package(&root.join(path), packages)?));
+    }
+    packages[index].1.dependencies = dependency_indices;
+
+    Ok(index)
+}
+
+impl Manifest {
+    fn from_toml(toml: &Table, root: &Path, file: &str) -> Result<Self, ManifestError> {
+        check_keys(toml, "", &["package", "build", "dependencies"], file)?;
+        let package = match toml.get("package") {
+            Some(Value::Table(package)) => package,
+            Some(_) => return Err(invalid(file, "package", "a table")),
+            None => return Err(missing(file, "package")),
+        };
+        check_keys(package, "package", &["name", "entry"], file)?;
+        let name = match package.get("name") {
+            Some(Value::String(name)) => name.clone(),
+            Some(_) => return Err(invalid(file, "package.name", "a string")),
+            None => return Err(missing(file, "package.name")),
+        };
+        let entry = match package.get("entry") {
+            Some(Value::String(entry)) => entry.as_str(),
+            Some(_) => return Err(invalid(file, "package.entry", "a string")),
+            None => "src/main.cara",
+        };
+
+        let build = match toml.get("build") {
+            Some(Value::Table(build)) => {
+                check_keys(
+                    build,
+                    "build",
+                    &["c-sources", "libraries", "library-paths"],
+                    file,
+                )?;
+                Some(build)
+            }
+            Some(_) => return Err(invalid(file, "build", "a table")),
+            None => None,
+        };
+        let strings = |key: &str| -> Result<Vec<String>, ManifestError> {
+            match build.and_then(|build| build.get(key)) {
+                Some(Value::Array(values)) => values
+                    .iter()
+                    .map(|value| match value {
+                        Value::String(value) => Ok(value.clone()),
+                        _ => Err(invalid(
+                            file,
+                            &format!("build.{}", key),
+                            "an array of strings",
+                        )),
+                    })
+                    .collect(),
+                Some(_) => Err(invalid(
+                    file,
+                    &format!("build.{}", key),
+                    "an array of strings",
+                )),
+                None => Ok(Vec::new()),
+            }
+        };
+
+        Ok(Self {
+            name,
+            root: root.to_owned(),
+            entry: root.join(entry),
+            dependencies: Vec::new(),
+            c_sources: strings("c-sources")?
+                .iter()
+                .map(|source| root.join(source))
+                .collect(),
+            libraries: strings("libraries")?,
+            library_paths: strings("library-paths")?
+                .iter()
+                .map(|path| root.join(path))
+                .collect(),
+        })
+    }
+}
+
+// Rejects keys the manifest doesn't use, which are most likely misspelled.
+fn check_keys(
+    table: &Table,
+    prefix: &str,
+    known: &[&str],
+    file: &str,
+) -> Result<(), ManifestError> {
+    match table.keys().find(|key| !known.contains(&key.as_str())) {
+        Some(key) if prefix.is_empty() => Err(ManifestError::UnknownKey {
+            file: file.to_owned(),
+            key: key.clone(),
+        }),
+        Some(key) => Err(ManifestError::UnknownKey {
+            file: file.to_owned(),
+            key: format!("{}.{}", prefix, key),
+        }),
+        None => Ok(()),
+    }
+}
+
+fn missing(file: &str, key: &str) -> ManifestError {
+    ManifestError::MissingKey {
+        file: file.to_owned(),
+        key: key.to_owned(),
+    }
+}
+
+fn invalid(file: &str, key: &str, expected: &'static str) -> ManifestError {
+    ManifestError::InvalidValue {
+        file: file.to_owned(),
+        key: key.to_owned(),
+        expected,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest(code: &str) -> Result<Manifest, ManifestError> {
+        Manifest::from_toml(&code.parse().unwrap(), Path::new("app"), MANIFEST_NAME)
+    }
+
+    fn key(error: ManifestError) -> String {
+        match error {
+            ManifestError::MissingKey { key, .. }
+            | ManifestError::UnknownKey { key, .. }
+            | ManifestError::InvalidValue { key, .. } => key,
+            _ => panic!("expected an error about a key, got {:?}", error),
+        }
+    }
+
+    #[test]
+    fn valid_manifest() {
+        let manifest = manifest(
+            r#"
+            [package]
+            name = "app"
+
+            [build]
+            c-sources = ["c/io.c"]
+            libraries = ["m"]
+            library-paths = ["lib"]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(manifest.name, "app");
+        assert_eq!(manifest.entry, Path::new("app/src/main.cara"));
+        assert_eq!(manifest.c_sources, [Path::new("app/c/io.c")]);
+        assert_eq!(manifest.libraries, ["m"]);
+        assert_eq!(manifest.library_paths, [Path::new("app/lib")]);
+    }
+
+    #[test]
+    fn missing_package() {
+        let error = manifest("[build]\nlibraries = []").unwrap_err();
+        assert!(matches!(error, ManifestError::MissingKey { .. }));
+        assert_eq!(key(error), "package");
+    }
+
+    #[test]
+    fn unknown_keys() {
+        let error = manifest("[package]\nname = \"app\"\nentyr = \"app.cara\"").unwrap_err();
+        assert!(matches!(error, ManifestError::UnknownKey { .. }));
+        assert_eq!(key(error), "package.entyr");
+
+        let error = manifest("[package]\nname = \"app\"\n[bulid]").unwrap_err();
+        assert_eq!(key(error), "bulid");
+    }
+
+    #[test]
+    fn invalid_values() {
+        let error = manifest("[package]\nname = 1").unwrap_err();
+        assert!(matches!(error, ManifestError::InvalidValue { .. }));
+        assert_eq!(key(error), "package.name");
+
+        let error = manifest("[package]\nname = \"app\"\n[build]\nlibraries = \"m\"").unwrap_err();
+        assert_eq!(key(error), "build.libraries");
+    }
+
+    #[test]
+    fn dependency_paths() {
+        let directory = std::env::temp_dir().join(format!("cara_packages_{}", std::process::id()));
+        let files = [
+            (
+                "app/Cara.toml",
+                "[package]\nname = \"app\"\n[dependencies]\nutil = { path = \"../libs/util\" }",
+            ),
+            (
+                "libs/util/Cara.toml",
+                "[package]\nname = \"util\"\nentry = \"util.cara\"\n[build]\nc-sources = [\"c/util.c\"]",
+            ),
+        ];
+        for (name, code) in files {
+            let path = directory.join(name);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, code).unwrap();
+        }
+        let packages = load_packages(&directory.join("app"));
+        std::fs::remove_dir_all(&directory).unwrap();
+
+        // Paths in a dependency's manifest are relative to that package.
+        let packages = packages.unwrap();
+        let util = directory.join("app/../libs/util");
+        assert_eq!(packages[0].dependencies, [("util".to_owned(), 1)]);
+        assert_eq!(packages[1].entry, util.join("util.cara"));
+        assert_eq!(packages[1].c_sources, [util.join("c/util.c")]);
+    }
+}
@@ -5,29 +5,63 @@ impl CParser {
     pub fn parse_decl(&self, rules: Pair<Rule>) -> Decl {
         let mut rules_iter = rules.clone().into_inner();
 
-        let decl = rules_iter.next().unwrap();
+        let mut docs = Vec::new();
+        let mut decl = rules_iter.next().unwrap();
+        while decl.as_rule() == Rule::doc_comment {
+            docs.push(self.parse_doc_comment(decl));
+            decl = rules_iter.next().unwrap();
+        }
+        let is_pub = decl.as_rule() == Rule::pub_decl;
+        if is_pub {
+            decl = rules_iter.next().unwrap();
+        }
 
         match decl.as_rule() {
-            Rule::var_decl => Decl::VarDecl(self.parse_var_decl(decl)),
-            Rule::const_decl => Decl::ConstDecl(self.parse_const_decl(decl)),
-            Rule::extern_function_def => {
-                Decl::ExternFunctionDef(self.parse_extern_function_def(decl))
-            }
+            Rule::var_decl => Decl::VarDecl(VarDecl {
+                docs,
+                is_pub,
+                ..self.parse_var_decl(decl)
+            }),
+            Rule::const_decl => Decl::ConstDecl(ConstDecl {
+                docs,
+                is_pub,
+                ..self.parse_const_decl(decl)
+            }),
+            Rule::extern_function_def => Decl::ExternFunctionDef(ExternFunctionDef {
+                docs,
+                is_pub,
+                ..self.parse_extern_function_def(decl)
+            }),
             _ => unimplemented!(),
         }
     }
 
+    // The text after `///`, without the single space that usually follows.
+    pub fn parse_doc_comment(&self, rules: Pair<Rule>) -> String {
+        let text = rules.into_inner().next().unwrap().as_str();
+        text.strip_prefix(' ').unwrap_or(text).to_string()
+    }
+
     pub fn parse_var_decl(&self, rules: Pair<Rule>) -> VarDecl {
         let mut rules_iter = rules.clone().into_inner();
 
         let id_rule = rules_iter.next().unwrap();
         let id = self.parse_ident(id_rule);
 
-        let var_type = self.parse_type(rules_iter.next().unwrap());
+        let mut next = rules_iter.next().unwrap();
+        let var_type = if next.as_rule() == Rule::types {
+            let var_type = self.parse_type(next);
+            next = rules_iter.next().unwrap();
+            Some(var_type)
+        } else {
+            None
+        };
 
-        let initial_value = self.parse_expr(rules_iter.next().unwrap());
+        let initial_value = self.parse_expr(next);
 
         VarDecl {
+            docs: Vec::new(),
+            is_pub: false,
             name: id,
             var_type,
             initial_value,
@@ -44,6 +78,8 @@ impl CParser {
         let initial_value = self.parse_const_initial_value(rules_iter.next().unwrap());
 
         ConstDecl {
+            docs: Vec::new(),
+            is_pub: false,
             name: id,
             initial_value,
             span: self.get_span(rules.as_span().clone()),
@@ -59,13 +95,20 @@ impl CParser {
             Rule::function_def => {
                 ConstInitialValueEnum::Function(self.parse_function_def(initial_value))
             }
+            Rule::struct_def => ConstInitialValueEnum::Struct(self.parse_struct_def(initial_value)),
+            Rule::enum_def => ConstInitialValueEnum::Enum(self.parse_enum_def(initial_value)),
+            Rule::union_def => ConstInitialValueEnum::Union(self.parse_union_def(initial_value)),
+            Rule::import => ConstInitialValueEnum::Import(Import {
+                path: self.parse_string(initial_value.clone().into_inner().next().unwrap()),
+                span: self.get_span(initial_value.as_span()),
+            }),
             Rule::const_exp => ConstInitialValueEnum::Exp(self.parse_const_exp(initial_value)),
             _ => unimplemented!(),
         };
 
         ConstInitialValue {
             value,
-            span: self.get_span(rules.as_span().clone()),
+            span: self.get_span(rules.as_span()),
         }
     }
 
@@ -87,10 +130,12 @@ impl CParser {
         };
 
         ExternFunctionDef {
+            docs: Vec::new(),
+            is_pub: false,
             name,
             params,
             return_type,
-            span: self.get_span(rules.as_span().clone()),
+            span: self.get_span(rules.as_span()),
         }
     }
 
@@ -115,19 +160,76 @@ impl CParser {
             params,
             return_type,
             block,
-            span: self.get_span(rules.as_span().clone()),
+            span: self.get_span(rules.as_span()),
         }
     }
 
     pub fn parse_param(&self, rules: Pair<Rule>) -> Param {
-        let mut rules_iter = rules.clone().into_inner();
+        let mut rules_iter = rules.clone().into_inner().peekable();
+        let is_const = rules_iter
+            .next_if(|rule| rule.as_rule() == Rule::const_param)
+            .is_some();
         let name = rules_iter.next().unwrap().as_str().to_string();
         let param_type = self.parse_type(rules_iter.next().unwrap());
-        let span = self.get_span(rules.as_span().clone());
+        let span = self.get_span(rules.as_span());
         Param {
+            is_const,
             name,
             param_type,
             span,
         }
     }
+
+    pub fn parse_struct_def(&self, rules: Pair<Rule>) -> StructDef {
+        StructDef {
+            fields: rules
+                .clone()
+                .into_inner()
+                .map(|field| self.parse_field(field))
+                .collect(),
+            span: self.get_span(rules.as_span()),
+        }
+    }
+
+    pub fn parse_union_def(&self, rules: Pair<Rule>) -> UnionDef {
+        UnionDef {
+            fields: rules
+                .clone()
+                .into_inner()
+                .map(|field| self.parse_field(field))
+                .collect(),
+            span: self.get_span(rules.as_span()),
+        }
+    }
+
+    pub fn parse_field(&self, rules: Pair<Rule>) -> Field {
+        let mut rules_iter = rules.clone().into_inner();
+        Field {
+            name: self.parse_ident(rules_iter.next().unwrap()),
+            field_type: self.parse_type(rules_iter.next().unwrap()),
+            span: self.get_span(rules.as_span()),
+        }
+    }
+
+    pub fn parse_enum_def(&self, rules: Pair<Rule>) -> EnumDef {
+        let mut rules_iter = rules.clone().into_inner();
+        let backing_type = self.parse_type(rules_iter.next().unwrap());
+
+        let variants = rules_iter
+            .map(|variant| {
+                let mut variant_iter = variant.clone().into_inner();
+                Variant {
+                    name: self.parse_ident(variant_iter.next().unwrap()),
+                    value: variant_iter.next().map(|value| self.parse_expr(value)),
+                    span: self.get_span(variant.as_span()),
+                }
+            })
+            .collect();
+
+        EnumDef {
+            backing_type,
+            variants,
+            span: self.get_span(rules.as_span()),
+        }
+    }
 }
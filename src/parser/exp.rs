@@ -3,16 +3,176 @@ use alloc::vec;
 
 impl CParser {
     pub fn parse_lval(&self, rules: Pair<Rule>) -> LVal {
-        let mut rules_iter = rules.clone().into_inner();
-        let id = rules_iter.next().unwrap();
         let span = self.get_span(rules.as_span().clone());
         LVal {
-            ids: vec![self.parse_ident(id)],
+            ids: rules
+                .clone()
+                .into_inner()
+                .map(|id| self.parse_ident(id))
+                .collect(),
             span,
             exp: None,
         }
     }
 
+    pub fn parse_array(&self, rules: Pair<Rule>) -> Array {
+        let span = self.get_span(rules.as_span());
+        let items = rules.clone().into_inner().collect::<Vec<_>>();
+
+        match items.last().unwrap().as_rule() {
+            Rule::const_exp => Array::Template(
+                self.parse_expr(items[0].clone()),
+                self.parse_const_exp(items[1].clone()),
+                span,
+            ),
+            _ => Array::List(
+                items
+                    .into_iter()
+                    .map(|item| self.parse_expr(item))
+                    .collect(),
+                span,
+            ),
+        }
+    }
+
+    pub fn parse_struct_literal(&self, rules: Pair<Rule>) -> StructLiteral {
+        let mut rules_iter = rules.clone().into_inner().peekable();
+        let mut ids = Vec::new();
+        while let Some(ident) = rules_iter.next_if(|rule| rule.as_rule() == Rule::ident) {
+            ids.push(self.parse_ident(ident));
+        }
+        let fields = rules_iter
+            .map(|field| {
+                let mut field_iter = field.clone().into_inner();
+                FieldInit {
+                    name: self.parse_ident(field_iter.next().unwrap()),
+                    value: self.parse_expr(field_iter.next().unwrap()),
+                    span: self.get_span(field.as_span()),
+                }
+            })
+            .collect();
+
+        StructLiteral {
+            ids,
+            fields,
+            span: self.get_span(rules.as_span()),
+        }
+    }
+
+    pub fn parse_number(&self, rules: Pair<Rule>) -> Exp {
+        let span = self.get_span(rules.as_span());
+        let mut number_iter = rules.into_inner();
+        let digits = number_iter.next().unwrap();
+        if digits.as_rule() == Rule::float_digits {
+            return Exp::Float(Box::new(Float {
+                num: digits.as_str().parse().unwrap(),
+                text: digits.as_str().into(),
+                span,
+            }));
+        }
+
+        let digits = digits.as_str().replace('_', "");
+        let (digits, radix) = match digits.get(..2) {
+            Some("0x") => (&digits[2..], 16),
+            Some("0o") => (&digits[2..], 8),
+            Some("0b") => (&digits[2..], 2),
+            _ => (&digits[..], 10),
+        };
+        // Anything too large for `u128` is far out of range of every type.
+        let num = u128::from_str_radix(digits, radix).unwrap_or(u128::MAX);
+        let suffix = number_iter.next().map(|suffix| IntSuffix {
+            bit_width: suffix.as_str()[1..].parse().unwrap(),
+            signed: suffix.as_str().starts_with('i'),
+        });
+        Exp::Number(Number { num, suffix, span })
+    }
+
+    pub fn parse_char(&self, rules: Pair<Rule>) -> Char {
+        let span = self.get_span(rules.as_span());
+        let char = rules.into_inner().next().unwrap();
+        let text = char.as_str();
+        let (num, unicode) = match char.as_rule() {
+            Rule::unicode_char => (
+                u32::from_str_radix(&text[3..text.len() - 1], 16).unwrap(),
+                true,
+            ),
+            Rule::escape_char => {
+                let num = match &text[1..] {
+                    "n" => b'\n',
+                    "r" => b'\r',
+                    "t" => b'\t',
+                    "0" => 0,
+                    escape if escape.starts_with('x') => {
+                        u8::from_str_radix(&escape[1..], 16).unwrap()
+                    }
+                    escape => escape.as_bytes()[0],
+                };
+                (num as u32, false)
+            }
+            // Anything beyond ASCII does not fit in a byte.
+            _ => {
+                let num = text.chars().next().unwrap() as u32;
+                (num, num > 0x7f)
+            }
+        };
+        Char { num, unicode, span }
+    }
+
+    pub fn parse_string(&self, rules: Pair<Rule>) -> Str {
+        let span = self.get_span(rules.as_span());
+        let inner = rules.into_inner().next().unwrap().as_str();
+        let mut value = String::new();
+        let mut invalid = None;
+        let mut chars = inner.chars();
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                value.push(c);
+                continue;
+            }
+            match chars.next().unwrap() {
+                'b' => value.push('\u{8}'),
+                'f' => value.push('\u{c}'),
+                'n' => value.push('\n'),
+                'r' => value.push('\r'),
+                't' => value.push('\t'),
+                'u' => {
+                    let code: String = chars.by_ref().take(4).collect();
+                    let code = u32::from_str_radix(&code, 16).unwrap();
+                    match char::from_u32(code) {
+                        Some(c) => value.push(c),
+                        None => {
+                            invalid.get_or_insert(code);
+                        }
+                    }
+                }
+                c => value.push(c),
+            }
+        }
+        Str {
+            value,
+            invalid,
+            span,
+        }
+    }
+
+    pub fn parse_deref(&self, rules: Pair<Rule>) -> Deref {
+        let mut rules_iter = rules.clone().into_inner();
+        let span = self.get_span(rules.as_span());
+        let first = rules_iter.next().unwrap();
+        let index = rules_iter.next().map(|index| self.parse_expr(index));
+
+        match (first.as_rule(), index) {
+            (Rule::lval, None) => Deref::DerefId(self.parse_lval(first), span),
+            (Rule::exp, None) => Deref::DerefExp(self.parse_expr(first), span),
+            (Rule::deref, None) => {
+                Deref::DerefExp(Exp::Deref(Box::new(self.parse_deref(first))), span)
+            }
+            (Rule::lval, Some(index)) => Deref::DerefPtr(self.parse_lval(first), index, span),
+            (Rule::exp, Some(index)) => Deref::DerefPtrExp(self.parse_expr(first), index, span),
+            _ => unimplemented!(),
+        }
+    }
+
     pub fn parse_const_exp(&self, rules: Pair<Rule>) -> ConstExp {
         let mut rules_iter = rules.clone().into_inner();
         let exp = rules_iter.next().unwrap();
@@ -23,23 +183,44 @@ impl CParser {
 
     pub fn parse_expr(&self, rules: Pair<Rule>) -> Exp {
         let pratt = PrattParser::new()
+            .op(Op::infix(Rule::or, Assoc::Left))
+            .op(Op::infix(Rule::and, Assoc::Left))
             .op(Op::infix(Rule::eq, Assoc::Left) | Op::infix(Rule::neq, Assoc::Left))
+            .op(Op::infix(Rule::lt, Assoc::Left)
+                | Op::infix(Rule::gt, Assoc::Left)
+                | Op::infix(Rule::le, Assoc::Left)
+                | Op::infix(Rule::ge, Assoc::Left))
+            // Unlike C, masks bind tighter than comparisons, so `a & b == c`
+            // compares `a & b`.
+            .op(Op::infix(Rule::bit_or, Assoc::Left))
+            .op(Op::infix(Rule::bit_xor, Assoc::Left))
+            .op(Op::infix(Rule::bit_and, Assoc::Left))
+            .op(Op::infix(Rule::shl, Assoc::Left) | Op::infix(Rule::shr, Assoc::Left))
             .op(Op::infix(Rule::add, Assoc::Left) | Op::infix(Rule::sub, Assoc::Left))
             .op(Op::infix(Rule::mul, Assoc::Left)
                 | Op::infix(Rule::div, Assoc::Left)
                 | Op::infix(Rule::r#mod, Assoc::Left))
             .op(Op::postfix(Rule::convert_type))
-            .op(Op::postfix(Rule::call))
-            .op(Op::prefix(Rule::neg) | Op::prefix(Rule::pos));
+            .op(Op::prefix(Rule::neg)
+                | Op::prefix(Rule::pos)
+                | Op::prefix(Rule::not)
+                | Op::prefix(Rule::bit_not)
+                | Op::prefix(Rule::get_addr))
+            .op(Op::postfix(Rule::call) | Op::postfix(Rule::index) | Op::postfix(Rule::member));
 
         pratt
             .map_primary(|primary| match primary.as_rule() {
                 Rule::exp => self.parse_expr(primary),
-                Rule::number => Exp::Number(Number {
-                    num: primary.as_str().parse().unwrap(),
-                    span: self.get_span(primary.as_span()),
-                }),
+                Rule::number => self.parse_number(primary.clone()),
+                Rule::bool => {
+                    Exp::Bool(primary.as_str() == "true", self.get_span(primary.as_span()))
+                }
+                Rule::character => Exp::Char(self.parse_char(primary.clone())),
+                Rule::string => Exp::Str(Box::new(self.parse_string(primary))),
+                Rule::array => Exp::Array(Box::new(self.parse_array(primary))),
+                Rule::struct_literal => Exp::Struct(Box::new(self.parse_struct_literal(primary))),
                 Rule::lval => Exp::LVal(Box::new(self.parse_lval(primary))),
+                Rule::deref => Exp::Deref(Box::new(self.parse_deref(primary))),
                 Rule::ident => Exp::LVal(Box::new(LVal {
                     ids: vec![self.parse_ident(primary.clone())],
                     span: self.get_span(primary.as_span()),
@@ -59,6 +240,14 @@ impl CParser {
                     Box::new(rhs),
                     self.get_span(op.as_span()),
                 ),
+                Rule::not => Exp::Unary(UnaryOp::Not, Box::new(rhs), self.get_span(op.as_span())),
+                Rule::bit_not => {
+                    Exp::Unary(UnaryOp::BitNot, Box::new(rhs), self.get_span(op.as_span()))
+                }
+                Rule::get_addr => Exp::GetAddr(Box::new(GetAddr {
+                    exp: rhs,
+                    span: self.get_span(op.as_span()),
+                })),
                 _ => unimplemented!(),
             })
             .map_postfix(|lhs, op| match op.as_rule() {
@@ -79,6 +268,29 @@ impl CParser {
                         span: self.get_span(op.as_span()),
                     }))
                 },
+                Rule::index => {
+                    let index = op.clone().into_inner().next().unwrap();
+                    if index.as_rule() == Rule::range {
+                        return Exp::Slice(Box::new(Slice {
+                            value: lhs,
+                            range: self.parse_range(index),
+                            span: self.get_span(op.as_span()),
+                        }));
+                    }
+                    Exp::Deref(Box::new(Deref::DerefPtrExp(
+                        lhs,
+                        self.parse_expr(index),
+                        self.get_span(op.as_span()),
+                    )))
+                }
+                Rule::member => {
+                    let field = self.parse_ident(op.clone().into_inner().next().unwrap());
+                    Exp::LVal(Box::new(LVal {
+                        ids: vec![field],
+                        span: self.get_span(op.as_span()),
+                        exp: Some(lhs),
+                    }))
+                }
                 _ => unimplemented!(),
             })
             .map_infix(|lhs, op, rhs| {
@@ -90,6 +302,17 @@ impl CParser {
                     match op.as_rule() {
                         Rule::eq => BinaryOp::Eq,
                         Rule::neq => BinaryOp::Neq,
+                        Rule::lt => BinaryOp::Lt,
+                        Rule::gt => BinaryOp::Gt,
+                        Rule::le => BinaryOp::Le,
+                        Rule::ge => BinaryOp::Ge,
+                        Rule::and => BinaryOp::And,
+                        Rule::or => BinaryOp::Or,
+                        Rule::bit_and => BinaryOp::BitAnd,
+                        Rule::bit_or => BinaryOp::BitOr,
+                        Rule::bit_xor => BinaryOp::BitXor,
+                        Rule::shl => BinaryOp::Shl,
+                        Rule::shr => BinaryOp::Shr,
                         Rule::add => BinaryOp::Add,
                         Rule::sub => BinaryOp::Sub,
                         Rule::mul => BinaryOp::Mul,
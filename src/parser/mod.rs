@@ -10,6 +10,8 @@ use pest_derive::Parser;
 mod decl;
 mod exp;
 mod stmt;
+#[cfg(test)]
+mod tests;
 
 #[derive(Parser)]
 #[grammar = "cara.pest"]
@@ -94,7 +96,58 @@ impl CParser {
     pub fn parse_type(&self, rules: Pair<Rule>) -> Type {
         let mut rules_iter = rules.clone().into_inner();
 
-        let vtype_enum = rules_iter.next().unwrap();
+        let mut stars = Vec::new();
+        let vtype_enum = loop {
+            let rule = rules_iter.next().unwrap();
+            if rule.as_rule() != Rule::star {
+                break rule;
+            }
+            let qualifiers: Vec<&str> = rule.into_inner().map(|rule| rule.as_str()).collect();
+            stars.push(Star {
+                is_const: qualifiers.contains(&"const"),
+                is_volatile: qualifiers.contains(&"volatile"),
+            });
+        };
+
+        if vtype_enum.as_rule() == Rule::array_type {
+            let mut array_iter = vtype_enum.into_inner();
+            let len = ConstExp {
+                exp: self.parse_expr(array_iter.next().unwrap()),
+            };
+            let element = self.parse_type(array_iter.next().unwrap());
+            return Type {
+                ty: TypeEnum::Array(Box::new(element), Box::new(len)),
+                star: stars,
+                span: self.get_span(rules.as_span()),
+            };
+        }
+
+        if vtype_enum.as_rule() == Rule::slice_type {
+            let mut slice_iter = vtype_enum.into_inner();
+            let mut element = slice_iter.next().unwrap();
+            let is_const = element.as_rule() == Rule::slice_const;
+            if is_const {
+                element = slice_iter.next().unwrap();
+            }
+            return Type {
+                ty: TypeEnum::Slice(Box::new(self.parse_type(element)), is_const),
+                star: stars,
+                span: self.get_span(rules.as_span()),
+            };
+        }
+
+        if vtype_enum.as_rule() == Rule::type_path {
+            return Type {
+                ty: TypeEnum::Named(
+                    vtype_enum
+                        .into_inner()
+                        .map(|ident| self.parse_ident(ident))
+                        .collect(),
+                ),
+                star: stars,
+                span: self.get_span(rules.as_span()),
+            };
+        }
 
         let vty_enum = match vtype_enum.as_str() {
             "u64" => TypeEnum::U64,
@@ -105,18 +158,17 @@ impl CParser {
             "i32" => TypeEnum::I32,
             "i16" => TypeEnum::I16,
             "i8" => TypeEnum::I8,
+            "f64" => TypeEnum::F64,
+            "f32" => TypeEnum::F32,
+            "bool" => TypeEnum::Bool,
             "void" => TypeEnum::Void,
             _ => panic!("Unkown type {}!", vtype_enum.as_str()),
         };
 
-        let mut star_cnt = 0usize;
-        while let Some(_) = rules_iter.next() {
-            star_cnt += 1;
-        }
         Type {
             ty: vty_enum,
-            star: star_cnt,
-            span: self.get_span(rules.as_span().clone()),
+            star: stars,
+            span: self.get_span(rules.as_span()),
         }
     }
 }
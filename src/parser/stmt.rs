@@ -16,6 +16,44 @@ impl CParser {
                 
                 exp
             },
+            Rule::if_stmt => {
+                Statement::If(Box::new(self.parse_if(rules.into_inner().next().unwrap())))
+            }
+            Rule::while_stmt => Statement::While(Box::new(
+                self.parse_while(rules.into_inner().next().unwrap()),
+            )),
+            Rule::for_stmt => {
+                Statement::For(Box::new(self.parse_for(rules.into_inner().next().unwrap())))
+            }
+            Rule::match_stmt => Statement::Match(Box::new(
+                self.parse_match(rules.into_inner().next().unwrap()),
+            )),
+            Rule::switch_stmt => Statement::Switch(Box::new(
+                self.parse_switch(rules.into_inner().next().unwrap()),
+            )),
+            Rule::assign_stmt => Statement::Assign(Box::new(
+                self.parse_assign(rules.into_inner().next().unwrap()),
+            )),
+            Rule::break_stmt => {
+                let rule = rules.clone().into_inner().next().unwrap();
+                Statement::Break(Break {
+                    label: rule
+                        .into_inner()
+                        .next()
+                        .map(|label| self.parse_label(label)),
+                    span: self.get_span(rules.as_span()),
+                })
+            }
+            Rule::continue_stmt => {
+                let rule = rules.clone().into_inner().next().unwrap();
+                Statement::Continue(Continue {
+                    label: rule
+                        .into_inner()
+                        .next()
+                        .map(|label| self.parse_label(label)),
+                    span: self.get_span(rules.as_span()),
+                })
+            }
             _ => unimplemented!(),
         };
         statement
@@ -32,4 +70,179 @@ impl CParser {
             span: self.get_span(rules.as_span().clone()),
         }
     }
+
+    pub fn parse_match(&self, rules: Pair<Rule>) -> Match {
+        let mut rules_iter = rules.clone().into_inner();
+        let value = self.parse_expr(rules_iter.next().unwrap());
+
+        let mut arms = Vec::new();
+        let mut else_block = None;
+        for arm in rules_iter {
+            let mut arm_iter = arm.clone().into_inner();
+            match arm.as_rule() {
+                Rule::match_arm => {
+                    let variant = self.parse_ident(arm_iter.next().unwrap());
+                    let mut rest = arm_iter.collect::<Vec<_>>();
+                    let block = self.parse_block(rest.pop().unwrap());
+                    arms.push(MatchArm {
+                        variant,
+                        binding: rest.pop().map(|binding| self.parse_ident(binding)),
+                        block,
+                        span: self.get_span(arm.as_span()),
+                    });
+                }
+                Rule::else_arm => else_block = Some(self.parse_block(arm_iter.next().unwrap())),
+                _ => unreachable!(),
+            }
+        }
+
+        Match {
+            value,
+            arms,
+            else_block,
+            span: self.get_span(rules.as_span()),
+        }
+    }
+
+    pub fn parse_switch(&self, rules: Pair<Rule>) -> Switch {
+        let mut rules_iter = rules.clone().into_inner();
+        let value = self.parse_expr(rules_iter.next().unwrap());
+
+        let mut arms = Vec::new();
+        let mut else_block = None;
+        for arm in rules_iter {
+            let mut arm_iter = arm.clone().into_inner();
+            match arm.as_rule() {
+                Rule::switch_arm => {
+                    let mut cases = arm_iter.collect::<Vec<_>>();
+                    let block = self.parse_block(cases.pop().unwrap());
+                    arms.push(SwitchArm {
+                        cases: cases
+                            .into_iter()
+                            .map(|case| {
+                                let case = case.into_inner().next().unwrap();
+                                match case.as_rule() {
+                                    Rule::range => SwitchCase::Range(self.parse_range(case)),
+                                    _ => SwitchCase::Value(self.parse_expr(case)),
+                                }
+                            })
+                            .collect(),
+                        block,
+                        span: self.get_span(arm.as_span()),
+                    });
+                }
+                Rule::else_arm => else_block = Some(self.parse_block(arm_iter.next().unwrap())),
+                _ => unreachable!(),
+            }
+        }
+
+        Switch {
+            value,
+            arms,
+            else_block,
+            span: self.get_span(rules.as_span()),
+        }
+    }
+
+    pub fn parse_if(&self, rules: Pair<Rule>) -> If {
+        let mut rules_iter = rules.clone().into_inner();
+        let condition = self.parse_expr(rules_iter.next().unwrap());
+        let then_block = self.parse_block(rules_iter.next().unwrap());
+        let else_block = rules_iter.next().map(|rule| match rule.as_rule() {
+            Rule::if_stmt => Else::If(Box::new(self.parse_if(rule))),
+            Rule::block => Else::Block(self.parse_block(rule)),
+            _ => unimplemented!(),
+        });
+        If {
+            condition,
+            then_block,
+            else_block,
+            span: self.get_span(rules.as_span()),
+        }
+    }
+
+    pub fn parse_while(&self, rules: Pair<Rule>) -> While {
+        let mut rules_iter = rules.clone().into_inner().peekable();
+        let label = rules_iter
+            .next_if(|rule| rule.as_rule() == Rule::label)
+            .map(|label| self.parse_label(label));
+        let condition = self.parse_expr(rules_iter.next().unwrap());
+        let block = self.parse_block(rules_iter.next().unwrap());
+        While {
+            label,
+            condition,
+            block,
+            span: self.get_span(rules.as_span()),
+        }
+    }
+
+    pub fn parse_for(&self, rules: Pair<Rule>) -> For {
+        let mut rules_iter = rules.clone().into_inner().peekable();
+        let label = rules_iter
+            .next_if(|rule| rule.as_rule() == Rule::label)
+            .map(|label| self.parse_label(label));
+        let var = self.parse_ident(rules_iter.next().unwrap());
+        let iter = rules_iter.next().unwrap();
+        let iter = match iter.as_rule() {
+            Rule::range => ForIter::Range(self.parse_range(iter)),
+            _ => ForIter::Array(self.parse_expr(iter)),
+        };
+        let block = self.parse_block(rules_iter.next().unwrap());
+        For {
+            label,
+            var,
+            iter,
+            block,
+            span: self.get_span(rules.as_span()),
+        }
+    }
+
+    pub fn parse_range(&self, rules: Pair<Rule>) -> Range {
+        let mut rules_iter = rules.clone().into_inner();
+        let start = self.parse_expr(rules_iter.next().unwrap());
+        let end = self.parse_expr(rules_iter.next().unwrap());
+        Range {
+            start,
+            end,
+            span: self.get_span(rules.as_span()),
+        }
+    }
+
+    pub fn parse_assign(&self, rules: Pair<Rule>) -> Assign {
+        let mut rules_iter = rules.clone().into_inner();
+
+        let target = self.parse_expr(rules_iter.next().unwrap());
+
+        let op = match rules_iter.next().unwrap().as_str() {
+            "=" => None,
+            "+=" => Some(BinaryOp::Add),
+            "-=" => Some(BinaryOp::Sub),
+            "*=" => Some(BinaryOp::Mul),
+            "/=" => Some(BinaryOp::Div),
+            "%=" => Some(BinaryOp::Mod),
+            "&=" => Some(BinaryOp::BitAnd),
+            "|=" => Some(BinaryOp::BitOr),
+            "^=" => Some(BinaryOp::BitXor),
+            "<<=" => Some(BinaryOp::Shl),
+            ">>=" => Some(BinaryOp::Shr),
+            _ => unimplemented!(),
+        };
+
+        let value = self.parse_expr(rules_iter.next().unwrap());
+
+        Assign {
+            target,
+            op,
+            value,
+            span: self.get_span(rules.as_span()),
+        }
+    }
+
+    pub fn parse_label(&self, rules: Pair<Rule>) -> Label {
+        let ident = rules.clone().into_inner().next().unwrap();
+        Label {
+            name: self.parse_ident(ident),
+            span: self.get_span(rules.as_span()),
+        }
+    }
 }
@@ -0,0 +1,74 @@
+use super::*;
+use alloc::vec;
+
+fn parse(code: &str) -> CompUnit {
+    CParser::new(code.into(), "test.cara".into()).parse()
+}
+
+// The doc comments of each global declaration.
+fn docs(code: &str) -> Vec<Vec<String>> {
+    parse(code)
+        .global_items
+        .into_iter()
+        .map(|item| match item {
+            GlobalItem::Decl(Decl::ConstDecl(decl)) => decl.docs,
+            GlobalItem::Decl(Decl::VarDecl(decl)) => decl.docs,
+            GlobalItem::Decl(_) => Vec::new(),
+        })
+        .collect()
+}
+
+// Writes out binary operations on names with explicit parentheses.
+fn grouping(code: &str) -> String {
+    fn write(exp: &Exp) -> String {
+        match exp {
+            Exp::Binary(lhs, op, rhs, _) => {
+                alloc::format!("({} {} {})", write(lhs), op, write(rhs))
+            }
+            Exp::LVal(lval) => lval.ids.join("."),
+            _ => unimplemented!(),
+        }
+    }
+
+    let code = alloc::format!("const x = {};", code);
+    match parse(&code).global_items.into_iter().next() {
+        Some(GlobalItem::Decl(Decl::ConstDecl(ConstDecl {
+            initial_value:
+                ConstInitialValue {
+                    value: ConstInitialValueEnum::Exp(const_exp),
+                    ..
+                },
+            ..
+        }))) => write(&const_exp.exp),
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn comparison_precedence() {
+    assert_eq!(grouping("a & b == c"), "((a & b) == c)");
+    assert_eq!(grouping("a | b != c ^ d"), "((a | b) != (c ^ d))");
+    assert_eq!(grouping("a < b == c >= d"), "((a < b) == (c >= d))");
+    assert_eq!(grouping("a + b < c << d"), "((a + b) < (c << d))");
+    assert_eq!(grouping("a < b && c"), "((a < b) && c)");
+}
+
+#[test]
+fn doc_comments() {
+    assert_eq!(
+        docs("/// One\n/// Two\n// Not a doc\npub const a = 1;\n//// Not a doc\nvar b = 2;"),
+        [vec!["One".to_string(), "Two".to_string()], Vec::new()]
+    );
+}
+
+#[test]
+fn stray_doc_comments() {
+    assert_eq!(
+        docs(
+            "const S = struct {\n    /// A field\n    x: i32,\n};
+            const main = fn() i32 {\n    var x = 1;\n    /// Before a statement\n    x = 2;\n    /// At the end\n};
+            /// At the end of the file"
+        ),
+        [Vec::<String>::new(), Vec::new()]
+    );
+}